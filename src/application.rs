@@ -1,13 +1,43 @@
 
 pub mod shape3d;
 pub mod axes;
+pub mod label;
+pub mod animation;
+pub mod recorder;
+pub mod turntable;
+pub mod hull;
+pub mod projection;
+pub mod hidden_line;
+pub mod culling;
+pub mod depth_cue;
+pub mod line;
+pub mod framebuffer;
+pub mod style;
+pub mod marker;
+pub mod point_cloud;
+pub mod model;
+pub mod layout;
+pub mod viewport;
+pub mod bounds;
+pub mod frustum;
+pub mod benchmark;
+pub mod parallel;
+pub mod mesh;
+pub mod catalogue;
 
-use std::error::Error;
+use std::{error::Error, path::{Path, PathBuf}, time::Instant};
 
 use sdl2::{keyboard::Scancode, event::{Event, WindowEvent}, pixels::Color};
-use glam::{DVec3, DMat4};
+use glam::DVec3;
 
-use crate::application::{shape3d::Shape3d, axes::Axes};
+use crate::application::{shape3d::Shape3d, axes::Axes, animation::{Timeline, Interpolation}, recorder::{Recorder, RecordFormat}, projection::{Projection, Lens}, hidden_line::{HiddenLines, HiddenLineMode}, culling::CullMode, depth_cue::DepthCue, line::{Stroke, LineBatch, LineTarget}, style::{Style, LinePattern}, marker::Markers, layout::Layout, viewport::Viewport, bounds::{BoundsOverlay, BoundingSphere}, frustum::{Frustum, Containment, CullingStats}, label::{Label, LabelFont}};
+
+const ANIMATION_PATH: &str = "animation.txt";
+const GIF_RECORDING_PATH: &str = "recording.gif";
+const PNG_RECORDING_DIRECTORY: &str = "recording";
+const VIEW_BORDER_COLOR: Color = Color::RGB(90, 90, 90);
+const ZOOM_STEP: f64 = 1.1; // per key press or mouse wheel notch
+const FRAME_FILL: f64 = 0.9; // fraction of the smallest view a framed selection spans
 
 #[derive(Debug)]
 enum Rotation {
@@ -19,12 +49,31 @@ enum Rotation {
 pub struct Application {
     event_pump: sdl2::EventPump,
     canvas: sdl2::render::Canvas<sdl2::video::Window>,
+    font: Option<LabelFont>,
     active: bool,
 
     // current state
     world_axes: Axes,
     shapes: Vec<Shape3d>,
-    perspective: DMat4,
+    initial_shapes: Vec<Shape3d>, // what a reset goes back to
+    lens: Lens, // picked with the projection key, views without a lens of their own use it
+    hidden_lines: HiddenLines,
+    cull_mode: CullMode,
+    depth_cue: DepthCue,
+    stroke: Stroke, // applied to every shape, kept so resets do not lose it
+    pattern: LinePattern, // likewise
+    markers: Markers,
+    layout: Layout,
+    pixels_per_unit: f64, // orthographic size of a world unit at zoom 1
+    zoom: f64,
+    target: DVec3, // world point at the middle of every view
+    selected: Option<usize>, // index into shapes, None selects them all
+    bounds_overlay: BoundsOverlay,
+    culling_stats: CullingStats, // of the last frame
+    hud_hidden: bool,
+    palette: bool, // each shape gets its own color from the style palette
+    timeline: Timeline,
+    recorder: Option<Recorder>,
 
     // next state
     rotation_center: DVec3,
@@ -32,10 +81,16 @@ pub struct Application {
     translation_axis: DVec3,
     delta_angle: f64,
     delta_location: f64,
+    delta_time: f64,
+    record_frame_rate: f64,
+    record_duration: Option<f64>,
 
     // flags
     rotation_type: Rotation, // types of rotations on the shapes are local, global
     shape_axes_hidden: bool,
+    labels_hidden: bool,
+    vertex_labels_hidden: bool,
+    interpolation: Interpolation, // used for tracks created by new keyframes
 }
 
 impl Application {
@@ -51,32 +106,186 @@ impl Application {
             .accelerated()
             .present_vsync()
             .build()?;
+        // the ttf context has to outlive every font loaded from it, and the font lives as long as the application
+        let ttf: &'static sdl2::ttf::Sdl2TtfContext = Box::leak(Box::new(sdl2::ttf::init()?));
+        // the same goes for the texture creator and the glyph textures the font keeps
+        let texture_creator = Box::leak(Box::new(canvas.texture_creator()));
+        let font = label::load_font(ttf).map(|font| LabelFont::new(font, texture_creator));
+        if font.is_none() {
+            println!("No font found, labels are disabled. Set WIRE_FRAME_FONT to a .ttf file to enable them");
+        }
 
-        let fov_y_radians = 30f64.to_radians();
-        let aspect_ratio = 1.0;
-        let z_near = 100.0;
-        let z_far = 0.0;
         return Ok(Self {
             event_pump,
             canvas,
+            font,
             active: true,
             world_axes: Axes::default(),
             // shapes: vec![shape3d::cube(50.0, DVec3 { x: 0.0, y: 0.0, z: 100.0 })], // testing perspective
             shapes: shape3d::platonic_solids(50.0),
-            perspective: DMat4::perspective_rh(fov_y_radians, aspect_ratio, z_near, z_far),
+            initial_shapes: shape3d::platonic_solids(50.0),
+            lens: Lens::Orthographic { scale: 1.0 },
+            hidden_lines: HiddenLines::default(),
+            cull_mode: CullMode::Off,
+            depth_cue: DepthCue::default(),
+            stroke: Stroke::default(),
+            pattern: LinePattern::Solid,
+            palette: false,
+            markers: Markers::default(),
+            layout: Layout::default(),
+            pixels_per_unit: 1.0,
+            zoom: 1.0,
+            target: DVec3::ZERO,
+            selected: None,
+            bounds_overlay: BoundsOverlay::Off,
+            culling_stats: CullingStats::default(),
+            hud_hidden: true,
+            timeline: Timeline::default(),
+            recorder: None,
             rotation_center: DVec3::ZERO, 
             rotation_axis: DVec3::ZERO,
             translation_axis: DVec3::ZERO,
             delta_angle: 0.05,
             delta_location: 5.0,
+            delta_time: 0.0,
+            record_frame_rate: 30.0,
+            record_duration: None,
             rotation_type: Rotation::Local,
             shape_axes_hidden: true,
+            labels_hidden: true,
+            vertex_labels_hidden: true,
+            interpolation: Interpolation::Slerp,
         });
     }
+
+    // duration of None records until the recording key is pressed again
+    pub fn set_recording(&mut self, frame_rate: f64, duration: Option<f64>) {
+        self.record_frame_rate = frame_rate;
+        self.record_duration = duration;
+    }
+
+    pub fn set_depth_cue_colors(&mut self, near_color: Color, far_color: Color) {
+        self.depth_cue.set_colors(near_color, far_color);
+    }
+
+    // None fits the depth range to the shapes in view every frame
+    pub fn set_depth_cue_range(&mut self, range: Option<(f64, f64)>) {
+        self.depth_cue.set_range(range);
+    }
+
+    pub fn set_stroke(&mut self, stroke: Stroke) {
+        self.stroke = stroke;
+        self.apply_styles();
+    }
+
+    // replaces the platonic solids shown at start up and after a reset
+    pub fn set_shapes(&mut self, shapes: Vec<Shape3d>) {
+        self.initial_shapes = shapes.clone();
+        self.shapes = shapes;
+        self.apply_styles();
+    }
+
+    pub fn set_pixels_per_unit(&mut self, pixels_per_unit: f64) {
+        self.pixels_per_unit = pixels_per_unit;
+    }
+
+    // draws a grid of at least the given number of edges directly and batched, plain and depth cued, and prints the time per frame of each
+    pub fn benchmark(&mut self, edges: usize, frames: usize) -> Result<(), Box<dyn Error>> {
+        let viewport = Viewport::from_canvas(&self.canvas)?;
+        let size = viewport.size().0.min(viewport.size().1) as f64 / viewport.pixel_scale() * FRAME_FILL;
+        let grid = benchmark::grid(edges, size);
+        println!("{} edges, {frames} frames", grid.edges().len());
+        for depth_cued in [false, true] {
+            let (direct, batched) = benchmark::compare(&mut self.canvas, &grid, frames, &self.stroke, depth_cued)?;
+            let name = if depth_cued { "Depth cued" } else { "Plain" };
+            println!("{name} direct: {:.2} ms/frame", direct.as_secs_f64() * 1000.0);
+            println!("{name} batched: {:.2} ms/frame", batched.as_secs_f64() * 1000.0);
+        }
+        return Ok(());
+    }
+
+    // steps of ZOOM_STEP, positive zooms in
+    fn zoom_by(&mut self, steps: i32) {
+        self.zoom = (self.zoom * ZOOM_STEP.powi(steps)).clamp(0.01, 100.0);
+        println!("Zoom {:.2}x", self.zoom);
+    }
+
+    // cycles through the shapes one at a time and then all of them together
+    fn select_next(&mut self) {
+        self.selected = match self.selected {
+            None if !self.shapes.is_empty() => Some(0),
+            Some(index) if index + 1 < self.shapes.len() => Some(index + 1),
+            _ => None,
+        };
+        match self.selected {
+            Some(index) => println!("Selected {}", self.shapes[index].name()),
+            None => println!("Selected All Shapes"),
+        }
+    }
+
+    // centers the views on the selection and zooms so its bounding box fits in the smallest view from any side
+    fn frame_selection(&mut self) -> Result<(), String> {
+        let bounds = match self.selected.and_then(|index| self.shapes.get(index)) {
+            Some(shape) => shape3d::bounding_box([shape]),
+            None => shape3d::bounding_box(&self.shapes),
+        };
+        let aabb = match bounds {
+            Some(aabb) => aabb,
+            None => return Ok(()),
+        };
+        self.target = aabb.center();
+        let radius = aabb.half_extents().length();
+        let viewport = Viewport::from_canvas(&self.canvas)?;
+        let view_size = self.layout.viewports(&viewport).iter()
+            .map(|(_, viewport)| viewport.size().0.min(viewport.size().1) as f64 / viewport.pixel_scale())
+            .fold(f64::MAX, f64::min);
+        if radius > 0.0 {
+            self.zoom = (view_size / 2.0 * FRAME_FILL / (radius * self.pixels_per_unit)).clamp(0.01, 100.0);
+        }
+        println!("Framed at {:.2}x Zoom", self.zoom);
+        return Ok(());
+    }
+
+    pub fn set_marker_size(&mut self, size: f64) {
+        self.markers.set_size(size);
+    }
+
+    pub fn set_pattern(&mut self, pattern: LinePattern) {
+        self.pattern = pattern;
+        self.apply_styles();
+    }
+
+    // keeps any edge or vertex colors the shapes came with
+    fn apply_styles(&mut self) {
+        for (index, shape) in self.shapes.iter_mut().enumerate() {
+            let style = shape.style_mut();
+            style.color = if self.palette { Style::from_palette(index).color } else { Style::default().color };
+            style.pattern = self.pattern;
+            style.stroke = self.stroke;
+        }
+    }
+
+    fn toggle_recording(&mut self, format: RecordFormat) {
+        match self.recorder.take() {
+            Some(recorder) => {
+                println!("Stopped recording, {} frames", recorder.finish());
+            }
+            None => {
+                match Recorder::new(format.clone(), self.record_frame_rate, self.record_duration) {
+                    Ok(recorder) => {
+                        self.recorder = Some(recorder);
+                        println!("Recording to {format:?}");
+                    }
+                    Err(error) => println!("Could not start recording: {error}"),
+                }
+            }
+        }
+    }
   
     fn handle_events(&mut self) -> Result<(), Box<dyn Error>>{
         self.handle_input();
-        for event in self.event_pump.poll_iter() {
+        let events: Vec<Event> = self.event_pump.poll_iter().collect();
+        for event in events {
             match event {
                 Event::Quit { .. } | Event::KeyDown { scancode: Some(Scancode::Escape), .. } => {
                     self.active = false;
@@ -84,8 +293,13 @@ impl Application {
                 Event::KeyDown { scancode: Some(scancode), .. } => {
                     match scancode {
                         Scancode::F1 => {
-                            self.shapes = shape3d::platonic_solids(50.0);
+                            self.shapes = self.initial_shapes.clone();
+                            self.apply_styles();
                             self.world_axes = Axes::default();
+                            self.timeline = Timeline::default();
+                            self.zoom = 1.0;
+                            self.target = DVec3::ZERO;
+                            self.selected = None;
                             println!("RESET!");
                         }
                         Scancode::F2 => {
@@ -119,18 +333,145 @@ impl Application {
                             self.shape_axes_hidden = !self.shape_axes_hidden;
                             println!("{}", if self.shape_axes_hidden {"Show Shape Axes"} else {"Hide shape Axes"});
                         }
+                        Scancode::F6 => {
+                            self.labels_hidden = !self.labels_hidden;
+                            println!("{}", if self.labels_hidden {"Hide Labels"} else {"Show Labels"});
+                        }
+                        Scancode::F7 => {
+                            self.vertex_labels_hidden = !self.vertex_labels_hidden;
+                            println!("{}", if self.vertex_labels_hidden {"Hide Vertex Indices"} else {"Show Vertex Indices"});
+                        }
+                        Scancode::F8 => {
+                            match self.timeline.save(Path::new(ANIMATION_PATH)) {
+                                Ok(()) => println!("Saved animation to {ANIMATION_PATH}"),
+                                Err(error) => println!("Could not save animation: {error}"),
+                            }
+                        }
+                        Scancode::F9 => {
+                            match Timeline::load(Path::new(ANIMATION_PATH)) {
+                                Ok(timeline) => {
+                                    self.timeline = timeline;
+                                    self.timeline.apply(&mut self.shapes);
+                                    println!("Loaded animation from {ANIMATION_PATH}");
+                                }
+                                Err(error) => println!("Could not load animation: {error}"),
+                            }
+                        }
+                        Scancode::F12 => {
+                            self.hud_hidden = !self.hud_hidden;
+                        }
+                        Scancode::F10 => {
+                            self.toggle_recording(RecordFormat::Gif(PathBuf::from(GIF_RECORDING_PATH)));
+                        }
+                        Scancode::F11 => {
+                            self.toggle_recording(RecordFormat::PngSequence(PathBuf::from(PNG_RECORDING_DIRECTORY)));
+                        }
+                        Scancode::P => {
+                            self.lens = self.lens.next();
+                            println!("{} Projection", self.lens.name());
+                        }
+                        Scancode::H => {
+                            self.hidden_lines.set_mode(self.hidden_lines.mode().next());
+                            println!("{}", self.hidden_lines.mode().name());
+                        }
+                        Scancode::O => {
+                            self.cull_mode = self.cull_mode.next();
+                            println!("{}", self.cull_mode.name());
+                        }
+                        Scancode::C => {
+                            self.depth_cue.toggle();
+                            println!("{}", if self.depth_cue.is_enabled() {"Depth Cueing On"} else {"Depth Cueing Off"});
+                        }
+                        Scancode::LeftBracket | Scancode::RightBracket => {
+                            let delta_width = if scancode == Scancode::LeftBracket { -0.5 } else { 0.5 };
+                            self.set_stroke(Stroke { width: (self.stroke.width + delta_width).clamp(1.0, 16.0), ..self.stroke });
+                            println!("Line Width {}", self.stroke.width);
+                        }
+                        Scancode::Backslash => {
+                            self.set_stroke(Stroke { antialiased: !self.stroke.antialiased, ..self.stroke });
+                            println!("{}", if self.stroke.antialiased {"Antialiasing On"} else {"Antialiasing Off"});
+                        }
+                        Scancode::Semicolon => {
+                            self.set_stroke(Stroke { cap: self.stroke.cap.next(), ..self.stroke });
+                            println!("{} Line Caps", self.stroke.cap.name());
+                        }
+                        Scancode::G => {
+                            self.set_pattern(self.pattern.next());
+                            println!("{} Lines", self.pattern.name());
+                        }
+                        Scancode::Y => {
+                            self.palette = !self.palette;
+                            self.apply_styles();
+                            println!("{}", if self.palette {"Palette Colors On"} else {"Palette Colors Off"});
+                        }
+                        Scancode::V => {
+                            self.markers.set_mode(self.markers.mode().next());
+                            println!("{}", self.markers.mode().name());
+                        }
+                        Scancode::M => {
+                            self.markers.set_shape(self.markers.shape().next());
+                            println!("{} Markers", self.markers.shape().name());
+                        }
+                        Scancode::Tab => {
+                            self.layout.toggle_split();
+                            println!("{}", if self.layout.is_split() {"Quad View"} else {"Single View"});
+                        }
+                        Scancode::Num1 | Scancode::Num2 | Scancode::Num3 | Scancode::Num4 => {
+                            let index = scancode as usize - Scancode::Num1 as usize;
+                            self.layout.maximize(index);
+                            println!("{} View", self.layout.views()[index].name);
+                        }
+                        Scancode::Equals | Scancode::KpPlus => self.zoom_by(1),
+                        Scancode::Minus | Scancode::KpMinus => self.zoom_by(-1),
+                        Scancode::Num0 => {
+                            self.zoom = 1.0;
+                            println!("Zoom 1x");
+                        }
+                        Scancode::B => {
+                            self.bounds_overlay = self.bounds_overlay.next();
+                            println!("{}", self.bounds_overlay.name());
+                        }
+                        Scancode::N => self.select_next(),
+                        Scancode::F => self.frame_selection()?,
+                        Scancode::K => {
+                            self.timeline.add_keyframes(&self.shapes, self.interpolation);
+                            println!("Keyframe at {:.2}s", self.timeline.time());
+                        }
+                        Scancode::I => {
+                            self.interpolation = self.interpolation.next();
+                            self.timeline.set_interpolation(self.interpolation);
+                            println!("{} Interpolation", self.interpolation.name());
+                        }
+                        Scancode::Space => {
+                            self.timeline.toggle_playing();
+                            println!("{}", if self.timeline.is_playing() {"Play"} else {"Pause"});
+                        }
+                        Scancode::L => {
+                            self.timeline.toggle_looping();
+                            println!("{}", if self.timeline.is_looping() {"Looping On"} else {"Looping Off"});
+                        }
+                        Scancode::Home => {
+                            self.timeline.rewind();
+                            self.timeline.apply(&mut self.shapes);
+                        }
+                        Scancode::Comma => {
+                            self.timeline.scrub(-0.1);
+                            self.timeline.apply(&mut self.shapes);
+                        }
+                        Scancode::Period => {
+                            self.timeline.scrub(0.1);
+                            self.timeline.apply(&mut self.shapes);
+                        }
                         _ => {}
                     }
                 }
+                Event::MouseWheel { y, .. } => {
+                    self.zoom_by(y);
+                }
                 Event::Window { win_event, .. } => {
                     match win_event { 
                         WindowEvent::Resized(width, height) => {
                             self.canvas.window_mut().set_size(width as u32, height as u32)?;
-                            let fov_y_radians = 0.7853981633974483; // pi/4
-                            let aspect_ratio = width as f64 / height as f64;
-                            let z_near = 10.0;
-                            let z_far = 410.0;
-                            self.perspective = DMat4::perspective_rh(fov_y_radians, aspect_ratio, z_near, z_far);
                         }
                         _ => {}
                     }
@@ -194,48 +535,184 @@ impl Application {
     }
     
     fn update_canvas(&mut self) {
+        if let Some(mut recorder) = self.recorder.take() {
+            let result = if recorder.tick(self.delta_time) { recorder.capture(&self.canvas) } else { Ok(()) };
+            if let Err(error) = result {
+                println!("Recording failed: {error}");
+            } else if recorder.is_finished() {
+                println!("Finished recording, {} frames", recorder.finish());
+            } else {
+                self.recorder = Some(recorder);
+            }
+        }
         self.canvas.present();
         self.canvas.set_draw_color(Color::BLACK);
         self.canvas.clear();
     }
     
     fn update_state(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.timeline.advance(self.delta_time) {
+            self.timeline.apply(&mut self.shapes);
+        }
+        // moving a shape only changes its transform, its vertices are transformed when they are next needed
+        let world_location = self.world_axes.location();
         for shape in self.shapes.iter_mut() {
             if self.shape_axes_hidden {
                 shape.show_axes();
             } else {
                 shape.hide_axes();
             }
-            match self.rotation_type {
-                Rotation::Local => {
-                    self.rotation_center = shape.location();
-                }
-                Rotation::Global => {
-                    self.rotation_center = self.world_axes.location();
-                }
-                Rotation::CoordSystem => {
-                    self.rotation_center = self.world_axes.location();
-                }
-            }
-            shape.rotate(&self.rotation_center, &self.rotation_axis, &self.delta_angle);
+            let rotation_center = if matches!(self.rotation_type, Rotation::Local) { shape.location() } else { world_location };
+            shape.rotate(&rotation_center, &self.rotation_axis, &self.delta_angle);
             shape.translate(&self.translation_axis, &self.delta_location);
-            shape.draw_orthographic(&mut self.canvas)?;
-            // shape.draw_weak_perspective(&mut self.canvas)?;
-            // shape.draw_perspective(&mut self.canvas, &self.perspective)?;
         }
         match self.rotation_type {
             Rotation::CoordSystem => {
+                self.rotation_center = world_location;
                 self.world_axes.rotate(&self.rotation_center, &self.rotation_axis, &self.delta_angle);
                 self.world_axes.translate(&self.translation_axis, &self.delta_location);
             }
             _ => {}
         }
-        self.world_axes.draw_orthographic(&mut self.canvas, &400.0)?;
+        self.draw_views()?;
+        return Ok(());
+    }
+
+    // every view projects the same shapes into its part of the window, turned the way the view looks at them
+    fn draw_views(&mut self) -> Result<(), String> {
+        let window_viewport = Viewport::from_canvas(&self.canvas)?;
+        self.culling_stats = CullingStats::default();
+        // the shapes moved since the last frame are transformed all at once before any view needs them
+        parallel::prepare(&self.shapes);
+        for (view, viewport) in self.layout.viewports(&window_viewport) {
+            let projection = view.projection(self.lens, self.pixels_per_unit * self.zoom, &self.target);
+            // every line of the view is collected first and reaches the renderer in one go, labels go on top
+            let mut batch = LineBatch::new(window_viewport.size());
+            self.draw_shapes(&mut batch, &viewport, &projection)?;
+            batch.next_layer();
+            self.world_axes.draw(&mut batch, &viewport, &400.0, &projection)?;
+            batch.next_layer();
+            self.draw_bounds(&mut batch, &viewport, &projection)?;
+            self.canvas.set_clip_rect(viewport.rect());
+            batch.submit(&mut self.canvas)?;
+            self.draw_labels(&viewport, &projection)?;
+            if self.layout.is_split() {
+                self.canvas.set_draw_color(VIEW_BORDER_COLOR);
+                self.canvas.draw_rect(viewport.rect())?;
+                if let Some(font) = &mut self.font {
+                    // the orthographic projection puts this in the top left corner whatever the view's own projection is
+                    let (width, height) = (viewport.size().0 as f64, viewport.size().1 as f64);
+                    let corner = DVec3::new(-width / 2.0, 20.0 - height / 2.0, 0.0) / viewport.pixel_scale();
+                    Label::new(view.name, corner, VIEW_BORDER_COLOR).draw(&mut self.canvas, font, &viewport, &Projection::new(Lens::Orthographic { scale: 1.0 }))?;
+                }
+            }
+        }
+        self.canvas.set_clip_rect(None);
+        if let (false, Some(font)) = (self.hud_hidden, &mut self.font) {
+            let (width, height) = (window_viewport.size().0 as f64, window_viewport.size().1 as f64);
+            let corner = DVec3::new(-width / 2.0, height / 2.0 - 4.0, 0.0) / window_viewport.pixel_scale();
+            Label::new(&self.culling_stats.text(), corner, Color::WHITE).draw(&mut self.canvas, font, &window_viewport, &Projection::new(Lens::Orthographic { scale: 1.0 }))?;
+        }
+        return Ok(());
+    }
+
+    fn draw_shapes(&mut self, batch: &mut LineBatch, viewport: &Viewport, projection: &Projection) -> Result<(), String> {
+        let shapes = &self.shapes;
+        // shapes whose bounding sphere is outside the view are skipped whole, the depth cue is fitted to the others
+        let frustum = Frustum::new(projection, viewport);
+        let spheres: Vec<Option<BoundingSphere>> = shapes.iter().map(|shape| shape.bounding_sphere()).collect();
+        let visible: Vec<bool> = spheres.iter()
+            .map(|sphere| sphere.is_some_and(|sphere| frustum.contains_sphere(&sphere) != Containment::Outside))
+            .collect();
+        self.depth_cue.fit(spheres.iter().zip(visible.iter()).filter_map(|(sphere, visible)| sphere.as_ref().filter(|_| *visible)), projection);
+        let visible_count = visible.iter().filter(|visible| **visible).count();
+        self.culling_stats.shapes_drawn += visible_count;
+        self.culling_stats.shapes_culled += shapes.len() - visible_count;
+        let visible_edges: usize = shapes.iter().zip(visible.iter()).filter(|(_, visible)| **visible).map(|(shape, _)| shape.edges().len()).sum();
+
+        let mut edges_inside = 0;
+        // hidden line removal already hides back edges, so it takes priority over culling
+        if self.hidden_lines.mode() != HiddenLineMode::Off {
+            for shape in shapes.iter() {
+                shape.draw_axes(batch, viewport, projection)?;
+            }
+            batch.next_layer();
+            if self.markers.draws_edges() {
+                edges_inside = self.hidden_lines.draw(batch, viewport, shapes, &visible, projection, &self.depth_cue)?;
+            }
+        } else {
+            for (shape, visible) in shapes.iter().zip(visible.iter()) {
+                if !*visible {
+                    continue;
+                }
+                // later shapes cover earlier ones where they overlap
+                batch.next_layer();
+                if !self.markers.draws_edges() {
+                    shape.draw_axes(batch, viewport, projection)?;
+                } else if self.cull_mode == CullMode::Off {
+                    edges_inside += shape.draw(batch, viewport, projection, &self.depth_cue)?;
+                } else {
+                    edges_inside += culling::draw(shape, batch, viewport, projection, self.cull_mode, &self.depth_cue)?;
+                }
+            }
+        }
+        if self.markers.draws_edges() {
+            self.culling_stats.edges_drawn += edges_inside;
+            self.culling_stats.edges_rejected += visible_edges - edges_inside;
+        }
+        for (shape, _) in shapes.iter().zip(visible.iter()).filter(|(shape, visible)| **visible && self.markers.draws_markers(shape)) {
+            batch.next_layer();
+            self.markers.draw(batch, shape, viewport, projection, &self.depth_cue)?;
+        }
+        return Ok(());
+    }
+
+    // axis aligned boxes stay aligned with the world axes in every view
+    fn draw_bounds(&mut self, batch: &mut LineBatch, viewport: &Viewport, projection: &Projection) -> Result<(), String> {
+        let stroke = Stroke::default();
+        for shape in self.shapes.iter() {
+            let edges = match self.bounds_overlay {
+                BoundsOverlay::Off => return Ok(()),
+                BoundsOverlay::Boxes => shape.aabb().map(|aabb| aabb.edges()),
+                BoundsOverlay::Spheres => shape.bounding_sphere().map(|sphere| sphere.edges()),
+                BoundsOverlay::Oriented => shape.obb().map(|obb| obb.edges()),
+            };
+            for [start, end] in edges.unwrap_or_default() {
+                if let Some((start, end)) = projection.project_segment(&start, &end, viewport) {
+                    batch.stroke_line(&start, &end, self.bounds_overlay.color(), &stroke)?;
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    fn draw_labels(&mut self, viewport: &Viewport, projection: &Projection) -> Result<(), String> {
+        let font = match &mut self.font {
+            Some(font) => font,
+            None => return Ok(()),
+        };
+        let mut labels = vec![];
+        if !self.labels_hidden {
+            labels.extend(self.world_axes.labels(&400.0));
+            labels.extend(self.shapes.iter().map(|shape| shape.name_label()));
+        }
+        if !self.vertex_labels_hidden {
+            for shape in self.shapes.iter() {
+                labels.extend(shape.vertex_labels());
+            }
+        }
+        for label in labels.iter() {
+            label.draw(&mut self.canvas, font, viewport, projection)?;
+        }
         return Ok(());
     }
 
     pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut last_frame = Instant::now();
         while self.active {
+            self.delta_time = last_frame.elapsed().as_secs_f64();
+            last_frame = Instant::now();
+
             self.handle_events()?;
             
             self.update_state()?;
@@ -0,0 +1,326 @@
+use std::{error::Error, fs, path::Path};
+
+use glam::{DVec3, DQuat};
+
+use crate::application::shape3d::Shape3d;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interpolation {
+    Linear, // lerps positions and scales, nlerps orientations
+    Slerp, // lerps positions and scales, slerps orientations
+    Cubic, // catmull-rom splines through positions and scales, squad through orientations
+}
+
+impl Interpolation {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Interpolation::Linear => "linear",
+            Interpolation::Slerp => "slerp",
+            Interpolation::Cubic => "cubic",
+        };
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        return match name {
+            "linear" => Some(Interpolation::Linear),
+            "slerp" => Some(Interpolation::Slerp),
+            "cubic" => Some(Interpolation::Cubic),
+            _ => None,
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            Interpolation::Linear => Interpolation::Slerp,
+            Interpolation::Slerp => Interpolation::Cubic,
+            Interpolation::Cubic => Interpolation::Linear,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time: f64,
+    pub location: DVec3,
+    pub orientation: DQuat,
+    pub scale: DVec3,
+}
+
+impl Keyframe {
+    pub fn from_shape(time: f64, shape: &Shape3d) -> Self {
+        return Self { time, location: shape.location(), orientation: shape.orientation(), scale: shape.scale() };
+    }
+}
+
+#[derive(Debug)]
+pub struct Track {
+    shape_index: usize,
+    interpolation: Interpolation,
+    keyframes: Vec<Keyframe>, // always sorted by time
+}
+
+impl Track {
+    pub fn new(shape_index: usize, interpolation: Interpolation) -> Self {
+        return Self { shape_index, interpolation, keyframes: vec![] };
+    }
+
+    pub fn shape_index(&self) -> usize {
+        return self.shape_index;
+    }
+
+    pub fn keyframes(&self) -> &Vec<Keyframe> {
+        return &self.keyframes;
+    }
+
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        self.interpolation = interpolation;
+    }
+
+    // a keyframe at the same time as an existing one replaces it
+    pub fn add_keyframe(&mut self, keyframe: Keyframe) {
+        match self.keyframes.iter().position(|existing| existing.time >= keyframe.time) {
+            Some(index) if self.keyframes[index].time == keyframe.time => self.keyframes[index] = keyframe,
+            Some(index) => self.keyframes.insert(index, keyframe),
+            None => self.keyframes.push(keyframe),
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        return self.keyframes.last().map_or(0.0, |keyframe| keyframe.time);
+    }
+
+    pub fn sample(&self, time: f64) -> Option<Keyframe> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        if time <= first.time {
+            return Some(Keyframe { time, ..*first });
+        }
+        if time >= last.time {
+            return Some(Keyframe { time, ..*last });
+        }
+        let index = self.keyframes.iter().position(|keyframe| keyframe.time > time)?;
+        let a = &self.keyframes[index - 1];
+        let b = &self.keyframes[index];
+        let t = (time - a.time) / (b.time - a.time);
+
+        let (location, orientation, scale) = match self.interpolation {
+            Interpolation::Linear => (
+                a.location.lerp(b.location, t),
+                nlerp(a.orientation, b.orientation, t),
+                a.scale.lerp(b.scale, t),
+            ),
+            Interpolation::Slerp => (
+                a.location.lerp(b.location, t),
+                a.orientation.slerp(b.orientation, t),
+                a.scale.lerp(b.scale, t),
+            ),
+            Interpolation::Cubic => {
+                // the end keyframes are repeated so the curve still passes through them
+                let before = &self.keyframes[index.saturating_sub(2)];
+                let after = &self.keyframes[(index + 1).min(self.keyframes.len() - 1)];
+                (
+                    catmull_rom(before.location, a.location, b.location, after.location, t),
+                    squad(before.orientation, a.orientation, b.orientation, after.orientation, t),
+                    catmull_rom(before.scale, a.scale, b.scale, after.scale, t),
+                )
+            }
+        };
+        return Some(Keyframe { time, location, orientation, scale });
+    }
+}
+
+#[derive(Debug)]
+pub struct Timeline {
+    tracks: Vec<Track>,
+    time: f64,
+    playing: bool,
+    looping: bool,
+}
+
+impl Timeline {
+    pub fn default() -> Self {
+        return Self { tracks: vec![], time: 0.0, playing: false, looping: true };
+    }
+
+    pub fn time(&self) -> f64 {
+        return self.time;
+    }
+
+    pub fn tracks(&self) -> &Vec<Track> {
+        return &self.tracks;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        return self.playing;
+    }
+
+    pub fn is_looping(&self) -> bool {
+        return self.looping;
+    }
+
+    pub fn duration(&self) -> f64 {
+        return self.tracks.iter().map(|track| track.duration()).fold(0.0, f64::max);
+    }
+
+    pub fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+    }
+
+    pub fn toggle_looping(&mut self) {
+        self.looping = !self.looping;
+    }
+
+    pub fn rewind(&mut self) {
+        self.time = 0.0;
+    }
+
+    pub fn scrub(&mut self, delta_time: f64) {
+        self.time = (self.time + delta_time).clamp(0.0, self.duration());
+    }
+
+    // returns true if the time changed and the shapes need to be updated
+    pub fn advance(&mut self, delta_time: f64) -> bool {
+        if !self.playing || self.tracks.is_empty() {
+            return false;
+        }
+        let duration = self.duration();
+        self.time += delta_time;
+        if self.time > duration {
+            if self.looping && duration > 0.0 {
+                self.time %= duration;
+            } else {
+                self.time = duration;
+                self.playing = false;
+            }
+        }
+        return true;
+    }
+
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        for track in self.tracks.iter_mut() {
+            track.set_interpolation(interpolation);
+        }
+    }
+
+    // keys the current transform of every shape at the current time
+    pub fn add_keyframes(&mut self, shapes: &[Shape3d], interpolation: Interpolation) {
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            let keyframe = Keyframe::from_shape(self.time, shape);
+            match self.tracks.iter_mut().find(|track| track.shape_index == shape_index) {
+                Some(track) => track.add_keyframe(keyframe),
+                None => {
+                    let mut track = Track::new(shape_index, interpolation);
+                    track.add_keyframe(keyframe);
+                    self.tracks.push(track);
+                }
+            }
+        }
+    }
+
+    pub fn apply(&self, shapes: &mut [Shape3d]) {
+        for track in self.tracks.iter() {
+            if let (Some(shape), Some(keyframe)) = (shapes.get_mut(track.shape_index), track.sample(self.time)) {
+                shape.set_transform(&keyframe.location, &keyframe.orientation, &keyframe.scale);
+            }
+        }
+    }
+
+    // one track header per shape followed by its keyframes:
+    // track <shape index> <interpolation>
+    // key <time> <location xyz> <orientation xyzw> <scale xyz>
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut contents = String::from("# wire_frame_rs animation\n");
+        for track in self.tracks.iter() {
+            contents += &format!("track {} {}\n", track.shape_index, track.interpolation.name());
+            for keyframe in track.keyframes.iter() {
+                let (l, o, s) = (keyframe.location, keyframe.orientation, keyframe.scale);
+                contents += &format!(
+                    "key {} {} {} {} {} {} {} {} {} {} {}\n",
+                    keyframe.time, l.x, l.y, l.z, o.x, o.y, o.z, o.w, s.x, s.y, s.z
+                );
+            }
+        }
+        fs::write(path, contents)?;
+        return Ok(());
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut timeline = Timeline::default();
+        for (line_number, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.first() {
+                None => {}
+                Some(word) if word.starts_with('#') => {}
+                Some(&"track") if words.len() == 3 => {
+                    let interpolation = Interpolation::from_name(words[2])
+                        .ok_or(format!("line {}: unknown interpolation {}", line_number + 1, words[2]))?;
+                    timeline.tracks.push(Track::new(words[1].parse()?, interpolation));
+                }
+                Some(&"key") if words.len() == 12 => {
+                    let track = timeline.tracks.last_mut()
+                        .ok_or(format!("line {}: keyframe before any track", line_number + 1))?;
+                    let mut values = [0.0; 11];
+                    for (value, word) in values.iter_mut().zip(words[1..].iter()) {
+                        *value = word.parse()?;
+                    }
+                    track.add_keyframe(Keyframe {
+                        time: values[0],
+                        location: DVec3::new(values[1], values[2], values[3]),
+                        orientation: DQuat::from_xyzw(values[4], values[5], values[6], values[7]).normalize(),
+                        scale: DVec3::new(values[8], values[9], values[10]),
+                    });
+                }
+                Some(_) => return Err(format!("line {}: could not parse \"{}\"", line_number + 1, line).into()),
+            }
+        }
+        return Ok(timeline);
+    }
+}
+
+fn nlerp(a: DQuat, b: DQuat, t: f64) -> DQuat {
+    // flip b into a's hemisphere so the shorter arc is taken
+    let b = if a.dot(b) < 0.0 { -b } else { b };
+    return a.lerp(b, t).normalize();
+}
+
+fn catmull_rom(p0: DVec3, p1: DVec3, p2: DVec3, p3: DVec3, t: f64) -> DVec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    return 0.5 * (
+        2.0 * p1 +
+        (p2 - p0) * t +
+        (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2 +
+        (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3
+    );
+}
+
+fn quat_log(q: DQuat) -> DVec3 {
+    let (axis, angle) = q.to_axis_angle();
+    return axis * (angle / 2.0);
+}
+
+fn quat_exp(v: DVec3) -> DQuat {
+    let half_angle = v.length();
+    if half_angle == 0.0 {
+        return DQuat::IDENTITY;
+    }
+    return DQuat::from_axis_angle(v / half_angle, half_angle * 2.0);
+}
+
+// control quaternion between q1's neighbours used by squad
+fn squad_control(q0: DQuat, q1: DQuat, q2: DQuat) -> DQuat {
+    let q1_inverse = q1.inverse();
+    let to_next = quat_log(q1_inverse * q2);
+    let to_previous = quat_log(q1_inverse * q0);
+    return (q1 * quat_exp(-(to_next + to_previous) / 4.0)).normalize();
+}
+
+fn squad(q0: DQuat, q1: DQuat, q2: DQuat, q3: DQuat, t: f64) -> DQuat {
+    // keep neighbours in the same hemisphere so the logarithms take the short way round
+    let q0 = if q1.dot(q0) < 0.0 { -q0 } else { q0 };
+    let q2 = if q1.dot(q2) < 0.0 { -q2 } else { q2 };
+    let q3 = if q2.dot(q3) < 0.0 { -q3 } else { q3 };
+    let s1 = squad_control(q0, q1, q2);
+    let s2 = squad_control(q1, q2, q3);
+    return q1.slerp(q2, t).slerp(s1.slerp(s2, t), 2.0 * t * (1.0 - t)).normalize();
+}
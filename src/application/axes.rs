@@ -1,21 +1,23 @@
 use glam::{DVec3, DQuat};
-use sdl2::{render::Canvas, video::Window, rect::Point, pixels::Color};
+use sdl2::pixels::Color;
+use crate::application::{label::Label, projection::Projection, viewport::Viewport, line::{Stroke, LineTarget}};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Axes {
     x: DVec3,
     y: DVec3,
     z: DVec3,
-    location: DVec3
+    location: DVec3,
+    colors: [Color; 3], // x, y and z
 }
 
 impl Axes {
     pub fn new(x: DVec3, y: DVec3, z: DVec3, location: DVec3) -> Self {
-        Self { x, y, z, location }
+        Self { x, y, z, location, colors: [Color::RED, Color::GREEN, Color::BLUE] }
     }
 
     pub fn default() -> Self {
-        Self { x: DVec3::X, y: DVec3::Y, z: DVec3::Z, location: DVec3::ZERO }
+        Self { x: DVec3::X, y: DVec3::Y, z: DVec3::Z, location: DVec3::ZERO, colors: [Color::RED, Color::GREEN, Color::BLUE] }
     }
 
     pub fn x(&self) -> &DVec3 {
@@ -34,6 +36,14 @@ impl Axes {
        return self.location; 
     }
 
+    pub fn colors(&self) -> [Color; 3] {
+        return self.colors;
+    }
+
+    pub fn set_colors(&mut self, colors: [Color; 3]) {
+        self.colors = colors;
+    }
+
     pub fn normalize_if_possible(&mut self) {
         if self.x.length() != 0.0 &&
            self.y.length() != 0.0 &&
@@ -65,18 +75,21 @@ impl Axes {
         }
     }
 
-    pub fn draw_orthographic(&self, canvas: &mut Canvas<Window>, scale: &f64) -> Result<(), String> {
-        let window_center = Point::new((canvas.window().size().0/2) as i32, (canvas.window().size().1/2) as i32);
-        let start = Point::new(self.location.x as i32, self.location.y as i32) + window_center;
-        let x_end = Point::new((self.x.x * scale) as i32, (self.x.y * scale) as i32) + start;
-        let y_end = Point::new((self.y.x * scale) as i32, (self.y.y * scale) as i32) + start;
-        let z_end = Point::new((self.z.x * scale) as i32, (self.z.y * scale) as i32) + start;
-        canvas.set_draw_color(Color::RED);
-        canvas.draw_line(start, x_end)?;        
-        canvas.set_draw_color(Color::GREEN);
-        canvas.draw_line(start, y_end)?;        
-        canvas.set_draw_color(Color::BLUE);
-        canvas.draw_line(start, z_end)?;   
+    pub fn labels(&self, scale: &f64) -> Vec<Label> {
+        return vec![
+            Label::new("X", self.location + self.x * (*scale), self.colors[0]),
+            Label::new("Y", self.location + self.y * (*scale), self.colors[1]),
+            Label::new("Z", self.location + self.z * (*scale), self.colors[2]),
+        ];
+    }
+
+    pub fn draw<T: LineTarget>(&self, target: &mut T, viewport: &Viewport, scale: &f64, projection: &Projection) -> Result<(), String> {
+        for (axis, color) in [(self.x, self.colors[0]), (self.y, self.colors[1]), (self.z, self.colors[2])] {
+            let end = self.location + axis * (*scale);
+            if let Some((start, end)) = projection.project_segment(&self.location, &end, viewport) {
+                target.stroke_line(&start, &end, color, &Stroke::default())?;
+            }
+        }
         return Ok(());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use glam::DVec3;
+use sdl2::{render::Canvas, video::Window, rect::Rect, pixels::PixelFormatEnum};
+
+use crate::application::{shape3d::Shape3d, projection::{Projection, Lens}, viewport::Viewport, depth_cue::DepthCue, line::{Stroke, LineBatch}};
+
+const TILT: f64 = 1.0; // radians the grid is turned about x when it is depth cued
+
+// flat square grid with at least the given number of edges, spanning size world units
+pub fn grid(edges: usize, size: f64) -> Shape3d {
+    // a grid of n by n cells has 2n(n + 1) edges
+    let mut cells = 1;
+    while 2 * cells * (cells + 1) < edges {
+        cells += 1;
+    }
+    let spacing = size / cells as f64;
+    let mut vertices = vec![];
+    for row in 0..=cells {
+        for column in 0..=cells {
+            vertices.push(DVec3::new(column as f64 * spacing - size / 2.0, row as f64 * spacing - size / 2.0, 0.0));
+        }
+    }
+    let index = |row: usize, column: usize| row * (cells + 1) + column;
+    let mut grid_edges = vec![];
+    for row in 0..=cells {
+        for column in 0..=cells {
+            if column < cells {
+                grid_edges.push([index(row, column), index(row, column + 1)]);
+            }
+            if row < cells {
+                grid_edges.push([index(row, column), index(row + 1, column)]);
+            }
+        }
+    }
+    let mut shape = Shape3d::new(vertices, grid_edges, DVec3::ZERO);
+    shape.set_name("grid");
+    return shape;
+}
+
+// average time per frame of drawing the shape with one renderer call per edge and through a line batch.
+// depth cued, the shape is tilted away from the viewer so the color changes along its edges and every edge is drawn
+// in pieces of their own color. frames are not presented, as vsync would hold both to the display refresh interval.
+// reading back a pixel instead makes the renderer finish drawing the frame before the next one starts
+pub fn compare(canvas: &mut Canvas<Window>, shape: &Shape3d, frames: usize, stroke: &Stroke, depth_cued: bool) -> Result<(Duration, Duration), String> {
+    let viewport = Viewport::from_canvas(canvas)?;
+    let projection = Projection::new(Lens::Orthographic { scale: 1.0 });
+    let mut depth_cue = DepthCue::default();
+    let mut shape = shape.clone();
+    shape.set_stroke(*stroke);
+    if depth_cued {
+        let center = shape.location();
+        shape.rotate(&center, &DVec3::X, &TILT);
+        depth_cue.toggle();
+        depth_cue.fit(shape.bounding_sphere().iter(), &projection);
+    }
+    let frames = frames.max(1);
+
+    let start = Instant::now();
+    for _ in 0..frames {
+        canvas.clear();
+        shape.draw(canvas, &viewport, &projection, &depth_cue)?;
+        finish(canvas)?;
+    }
+    let direct = start.elapsed() / frames as u32;
+
+    let start = Instant::now();
+    for _ in 0..frames {
+        canvas.clear();
+        let mut batch = LineBatch::new(viewport.size());
+        shape.draw(&mut batch, &viewport, &projection, &depth_cue)?;
+        batch.submit(canvas)?;
+        finish(canvas)?;
+    }
+    let batched = start.elapsed() / frames as u32;
+    canvas.present();
+    return Ok((direct, batched));
+}
+
+fn finish(canvas: &Canvas<Window>) -> Result<(), String> {
+    canvas.read_pixels(Rect::new(0, 0, 1, 1), PixelFormatEnum::RGBA8888)?;
+    return Ok(());
+}
@@ -0,0 +1,221 @@
+use glam::{DVec3, DMat3};
+use sdl2::pixels::Color;
+
+const SPHERE_SEGMENTS: usize = 32; // straight pieces per great circle of a drawn sphere
+const JACOBI_SWEEPS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl Aabb {
+    // None without any points
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a DVec3>) -> Option<Self> {
+        return points.into_iter().fold(None, |aabb: Option<Self>, point| match aabb {
+            None => Some(Self { min: *point, max: *point }),
+            Some(aabb) => Some(Self { min: aabb.min.min(*point), max: aabb.max.max(*point) }),
+        });
+    }
+
+    pub fn center(&self) -> DVec3 {
+        return (self.min + self.max) / 2.0;
+    }
+
+    pub fn half_extents(&self) -> DVec3 {
+        return (self.max - self.min) / 2.0;
+    }
+
+    pub fn union(&self, other: &Aabb) -> Self {
+        return Self { min: self.min.min(other.min), max: self.max.max(other.max) };
+    }
+
+    pub fn edges(&self) -> Vec<[DVec3; 2]> {
+        return box_edges(self.center(), [DVec3::X, DVec3::Y, DVec3::Z], self.half_extents());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingSphere {
+    pub center: DVec3,
+    pub radius: f64,
+}
+
+impl BoundingSphere {
+    // ritter's approximation, starts from two far apart points and grows to take in any point left outside.
+    // at most a few percent larger than the minimal sphere for typical shapes
+    pub fn from_points(points: &[DVec3]) -> Option<Self> {
+        let first = points.first()?;
+        let farthest_from = |from: DVec3| *points.iter()
+            .max_by(|a, b| a.distance_squared(from).total_cmp(&b.distance_squared(from)))
+            .unwrap_or(first);
+        let a = farthest_from(*first);
+        let b = farthest_from(a);
+        let mut sphere = Self { center: (a + b) / 2.0, radius: a.distance(b) / 2.0 };
+        for point in points.iter() {
+            let distance = point.distance(sphere.center);
+            if distance > sphere.radius {
+                // move the center towards the point just enough to reach it with the far side staying put
+                let radius = (sphere.radius + distance) / 2.0;
+                sphere.center += (*point - sphere.center) * ((radius - sphere.radius) / distance);
+                sphere.radius = radius;
+            }
+        }
+        return Some(sphere);
+    }
+
+    pub fn contains(&self, point: &DVec3) -> bool {
+        return point.distance(self.center) <= self.radius;
+    }
+
+    // three great circles, one around each world axis
+    pub fn edges(&self) -> Vec<[DVec3; 2]> {
+        let mut edges = Vec::with_capacity(3 * SPHERE_SEGMENTS);
+        for (u, v) in [(DVec3::X, DVec3::Y), (DVec3::Y, DVec3::Z), (DVec3::Z, DVec3::X)] {
+            let point = |step: usize| {
+                let angle = std::f64::consts::TAU * step as f64 / SPHERE_SEGMENTS as f64;
+                self.center + (u * angle.cos() + v * angle.sin()) * self.radius
+            };
+            edges.extend((0..SPHERE_SEGMENTS).map(|step| [point(step), point(step + 1)]));
+        }
+        return edges;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb {
+    pub center: DVec3,
+    pub axes: [DVec3; 3], // orthonormal
+    pub half_extents: DVec3, // along each of the axes
+}
+
+impl Obb {
+    // the tightest box around the points with its sides along the given orthonormal axes
+    pub fn from_axes(points: &[DVec3], axes: [DVec3; 3]) -> Option<Self> {
+        let first = points.first()?;
+        let along = |point: &DVec3| DVec3::new(point.dot(axes[0]), point.dot(axes[1]), point.dot(axes[2]));
+        let (min, max) = points.iter().fold((along(first), along(first)), |(min, max), point| (min.min(along(point)), max.max(along(point))));
+        let middle = (min + max) / 2.0;
+        return Some(Self {
+            center: axes[0] * middle.x + axes[1] * middle.y + axes[2] * middle.z,
+            axes,
+            half_extents: (max - min) / 2.0,
+        });
+    }
+
+    // axes along the principal components of the points, the directions they spread out the most and least in
+    pub fn from_points(points: &[DVec3]) -> Option<Self> {
+        let mean = points.iter().sum::<DVec3>() / points.len().max(1) as f64;
+        let mut covariance = DMat3::ZERO;
+        for point in points.iter() {
+            let offset = *point - mean;
+            covariance += DMat3::from_cols(offset * offset.x, offset * offset.y, offset * offset.z);
+        }
+        return Self::from_axes(points, principal_axes(covariance));
+    }
+
+    pub fn volume(&self) -> f64 {
+        return 8.0 * self.half_extents.x * self.half_extents.y * self.half_extents.z;
+    }
+
+    pub fn edges(&self) -> Vec<[DVec3; 2]> {
+        return box_edges(self.center, self.axes, self.half_extents);
+    }
+}
+
+// eigenvectors of a symmetric matrix by cyclic jacobi rotations, as a right handed orthonormal basis
+fn principal_axes(matrix: DMat3) -> [DVec3; 3] {
+    let mut a = matrix.to_cols_array_2d();
+    let mut vectors = DMat3::IDENTITY.to_cols_array_2d();
+    for _ in 0..JACOBI_SWEEPS {
+        let off_diagonal = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+        if off_diagonal < 1e-12 {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if a[p][q].abs() < 1e-15 {
+                continue;
+            }
+            // the rotation in the p q plane that zeroes a[p][q]
+            let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let t = if theta == 0.0 { 1.0 } else { t };
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            for row in a.iter_mut() {
+                let (akp, akq) = (row[p], row[q]);
+                row[p] = c * akp - s * akq;
+                row[q] = s * akp + c * akq;
+            }
+            let (row_p, row_q) = (a[p], a[q]);
+            a[p] = [0, 1, 2].map(|k| c * row_p[k] - s * row_q[k]);
+            a[q] = [0, 1, 2].map(|k| s * row_p[k] + c * row_q[k]);
+            for vector in vectors.iter_mut() {
+                let (vp, vq) = (vector[p], vector[q]);
+                vector[p] = c * vp - s * vq;
+                vector[q] = s * vp + c * vq;
+            }
+        }
+    }
+    // the rows of the accumulated rotation are the eigenvectors
+    let row = |index: usize| DVec3::new(vectors[0][index], vectors[1][index], vectors[2][index]).normalize_or_zero();
+    let (x, y) = (row(0), row(1));
+    return [x, y, x.cross(y)];
+}
+
+fn box_edges(center: DVec3, axes: [DVec3; 3], half_extents: DVec3) -> Vec<[DVec3; 2]> {
+    let corner = |signs: [f64; 3]| center
+        + axes[0] * (half_extents.x * signs[0])
+        + axes[1] * (half_extents.y * signs[1])
+        + axes[2] * (half_extents.z * signs[2]);
+    let mut edges = Vec::with_capacity(12);
+    // every corner connects to the corners that differ in one sign, each edge is taken from its lower corner
+    for index in 0..8 {
+        let signs = [0, 1, 2].map(|axis| if index & (1 << axis) == 0 { -1.0 } else { 1.0 });
+        for axis in 0..3 {
+            if signs[axis] < 0.0 {
+                let mut other = signs;
+                other[axis] = 1.0;
+                edges.push([corner(signs), corner(other)]);
+            }
+        }
+    }
+    return edges;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundsOverlay {
+    Off,
+    Boxes, // axis aligned bounding boxes
+    Spheres,
+    Oriented, // oriented bounding boxes
+}
+
+impl BoundsOverlay {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            BoundsOverlay::Off => "Bounding Volumes Off",
+            BoundsOverlay::Boxes => "Axis Aligned Bounding Boxes",
+            BoundsOverlay::Spheres => "Bounding Spheres",
+            BoundsOverlay::Oriented => "Oriented Bounding Boxes",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            BoundsOverlay::Off => BoundsOverlay::Boxes,
+            BoundsOverlay::Boxes => BoundsOverlay::Spheres,
+            BoundsOverlay::Spheres => BoundsOverlay::Oriented,
+            BoundsOverlay::Oriented => BoundsOverlay::Off,
+        };
+    }
+
+    pub fn color(&self) -> Color {
+        return match self {
+            BoundsOverlay::Off | BoundsOverlay::Boxes => Color::RGB(0, 200, 200),
+            BoundsOverlay::Spheres => Color::RGB(200, 120, 0),
+            BoundsOverlay::Oriented => Color::RGB(200, 0, 200),
+        };
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::{Arc, OnceLock};
+
+use glam::DVec3;
+
+use crate::application::{shape3d::Shape3d, mesh::Mesh};
+
+// one solid per line: name|x,y,z x,y,z ...|a,b,c a,b,c,d ...
+// the data has y up and faces counter-clockwise seen from outside. world y points down the screen, so y is flipped
+// when an entry is parsed and pyramids and cupolae still point up the screen.
+// johnson.txt has unit edges and lists the solids in the usual J1 to J92 order, catalan.txt has the duals of
+// the archimedean solids in the order shape3d::archimedean_solids lays them out
+const JOHNSON: &str = include_str!("catalogue/johnson.txt");
+const CATALAN: &str = include_str!("catalogue/catalan.txt");
+
+struct Entry {
+    name: &'static str,
+    mesh: Arc<Mesh>,
+}
+
+fn johnson_entries() -> &'static Vec<Entry> {
+    static ENTRIES: OnceLock<Vec<Entry>> = OnceLock::new();
+    return ENTRIES.get_or_init(|| parse(JOHNSON));
+}
+
+fn catalan_entries() -> &'static Vec<Entry> {
+    static ENTRIES: OnceLock<Vec<Entry>> = OnceLock::new();
+    return ENTRIES.get_or_init(|| parse(CATALAN));
+}
+
+// the data is part of the binary, so a line that does not parse is a bug rather than an input error
+fn parse(data: &'static str) -> Vec<Entry> {
+    return data.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_entry(line).unwrap_or_else(|| panic!("malformed catalogue entry: {line}")))
+        .collect();
+}
+
+// scaled so the farthest vertex is 1 from the center, like the platonic and archimedean solids
+fn parse_entry(line: &'static str) -> Option<Entry> {
+    let mut fields = line.trim().split('|');
+    let name = fields.next()?;
+    let vertices = fields.next()?.split_whitespace()
+        .map(|vertex| {
+            let coordinates: Vec<f64> = vertex.split(',').map(|value| value.parse().ok()).collect::<Option<_>>()?;
+            return match coordinates[..] {
+                [x, y, z] => Some(DVec3::new(x, y, z)),
+                _ => None,
+            };
+        })
+        .collect::<Option<Vec<DVec3>>>()?;
+    // flipping y mirrors the solid, so every face is reversed to keep it counter-clockwise seen from outside
+    let faces = fields.next()?.split_whitespace()
+        .map(|face| face.split(',').rev().map(|index| index.parse().ok().filter(|index| *index < vertices.len())).collect())
+        .collect::<Option<Vec<Vec<usize>>>>()?;
+    let radius = vertices.iter().map(|vertex| vertex.length()).fold(0.0, f64::max);
+    if radius <= 0.0 {
+        return None;
+    }
+    let vertices = vertices.iter().map(|vertex| DVec3::new(vertex.x, -vertex.y, vertex.z) / radius).collect();
+    return Some(Entry { name, mesh: Arc::new(Mesh::with_faces(vertices, faces)) });
+}
+
+fn shape(entry: &Entry, scale: f64, location: DVec3) -> Shape3d {
+    let mut shape = Shape3d::from_mesh(entry.mesh.clone(), location, DVec3::splat(scale));
+    shape.set_name(entry.name);
+    return shape;
+}
+
+// number is the solid's usual J number, 1 for the square pyramid up to 92 for the triangular hebesphenorotunda
+pub fn johnson(number: usize, scale: f64, location: DVec3) -> Option<Shape3d> {
+    let entry = johnson_entries().get(number.checked_sub(1)?)?;
+    return Some(shape(entry, scale, location));
+}
+
+// number 1 to 13 is the dual of the archimedean solid at the same place in shape3d::archimedean_solids,
+// 1 for the triakis tetrahedron up to 13 for the pentagonal hexecontahedron
+pub fn catalan(number: usize, scale: f64, location: DVec3) -> Option<Shape3d> {
+    let entry = catalan_entries().get(number.checked_sub(1)?)?;
+    return Some(shape(entry, scale, location));
+}
+
+pub fn johnson_names() -> Vec<&'static str> {
+    return johnson_entries().iter().map(|entry| entry.name).collect();
+}
+
+pub fn catalan_names() -> Vec<&'static str> {
+    return catalan_entries().iter().map(|entry| entry.name).collect();
+}
+
+// a name like elongated_square_pyramid or rhombic_dodecahedron, or an index like J8 or C2
+pub fn from_name(name: &str, scale: f64, location: DVec3) -> Option<Shape3d> {
+    let lower = name.to_lowercase();
+    if let Some(number) = lower.strip_prefix('j').and_then(|number| number.parse().ok()) {
+        return johnson(number, scale, location);
+    }
+    if let Some(number) = lower.strip_prefix('c').and_then(|number| number.parse().ok()) {
+        return catalan(number, scale, location);
+    }
+    return johnson_entries().iter()
+        .chain(catalan_entries().iter())
+        .find(|entry| entry.name == lower)
+        .map(|entry| shape(entry, scale, location));
+}
+
+// all 92 in rows of ten, each vertex at most 0.6 scale from its center so the rows fit the window
+pub fn johnson_solids(scale: f64) -> Vec<Shape3d> {
+    return grid(johnson_entries(), 10, scale * 0.6, scale * 1.5);
+}
+
+// all 13 in rows of five, the same size and spacing as the archimedean solids they are the duals of
+pub fn catalan_solids(scale: f64) -> Vec<Shape3d> {
+    return grid(catalan_entries(), 5, scale * 1.2, scale * 3.0);
+}
+
+// rows centered on the origin at z 100 like the other scenes, a short last row is centered as well
+fn grid(entries: &[Entry], columns: usize, radius: f64, spacing: f64) -> Vec<Shape3d> {
+    let rows = entries.len().div_ceil(columns);
+    return entries.iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let (row, column) = (index / columns, index % columns);
+            let in_row = (entries.len() - row * columns).min(columns);
+            let x = (column as f64 - (in_row - 1) as f64 / 2.0) * spacing;
+            let y = (row as f64 - (rows - 1) as f64 / 2.0) * spacing;
+            return shape(entry, radius, DVec3::new(x, y, 100.0));
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_entry_parses() {
+        assert_eq!(johnson_entries().len(), 92);
+        assert_eq!(catalan_entries().len(), 13);
+        assert_eq!(johnson_names()[0], "square_pyramid");
+        assert!(from_name("J92", 1.0, DVec3::ZERO).is_some());
+        assert!(from_name("C13", 1.0, DVec3::ZERO).is_some());
+        assert!(from_name("J93", 1.0, DVec3::ZERO).is_none());
+        assert!(from_name("C0", 1.0, DVec3::ZERO).is_none());
+    }
+
+    // every solid is convex around its center, so each face has to point away from it
+    #[test]
+    fn faces_point_outwards() {
+        for entry in johnson_entries().iter().chain(catalan_entries().iter()) {
+            let vertices = entry.mesh.vertices();
+            for face in entry.mesh.faces().iter() {
+                let mut normal = DVec3::ZERO;
+                for (index, vertex_index) in face.iter().enumerate() {
+                    normal += vertices[*vertex_index].cross(vertices[face[(index + 1) % face.len()]]);
+                }
+                let center = face.iter().map(|index| vertices[*index]).sum::<DVec3>() / face.len() as f64;
+                assert!(normal.dot(center) > 0.0, "{} has a face pointing inwards", entry.name);
+            }
+        }
+    }
+
+    // world y points down the screen, so the apex has the lowest y
+    #[test]
+    fn pyramids_point_up_the_screen() {
+        for number in [1, 2] {
+            let vertices = johnson_entries()[number - 1].mesh.vertices();
+            let lowest = vertices.iter().map(|vertex| vertex.y).fold(f64::MAX, f64::min);
+            assert_eq!(vertices.iter().filter(|vertex| vertex.y == lowest).count(), 1);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, projection::Projection, viewport::Viewport, depth_cue::DepthCue, line::{Stroke, LineTarget}, style};
+
+const SILHOUETTE_COLOR: Color = Color::YELLOW;
+const SILHOUETTE_EXTRA_WIDTH: f64 = 1.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CullMode {
+    Off, // every edge is drawn
+    BackFaces, // edges between faces that all face away from the viewer are hidden
+    Silhouettes, // back edges are hidden and edges between a front and a back face are emphasized
+}
+
+impl CullMode {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            CullMode::Off => "Back Face Culling Off",
+            CullMode::BackFaces => "Back Face Culling On",
+            CullMode::Silhouettes => "Silhouette Edges",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            CullMode::Off => CullMode::BackFaces,
+            CullMode::BackFaces => CullMode::Silhouettes,
+            CullMode::Silhouettes => CullMode::Off,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeFacing {
+    Front, // every adjacent face is facing the viewer
+    Back, // every adjacent face is facing away
+    Silhouette, // at least one adjacent face each way
+    Unknown, // the edge does not belong to any face
+}
+
+// classifies each edge of the shape, in the same order as Shape3d::edges
+pub fn edge_facings(shape: &Shape3d, projection: &Projection) -> Vec<EdgeFacing> {
+    let face_is_front: Vec<bool> = (0..shape.faces().len())
+        .map(|face_index| {
+            match (shape.face_normal(face_index), shape.face_center(face_index)) {
+                (Some(normal), Some(center)) => normal.dot(projection.view_direction(&center)) < 0.0,
+                _ => false,
+            }
+        })
+        .collect();
+    return shape.edge_faces().iter()
+        .map(|faces| {
+            let front = faces.iter().filter(|face_index| face_is_front[**face_index]).count();
+            match (front, faces.len()) {
+                (_, 0) => EdgeFacing::Unknown,
+                (0, _) => EdgeFacing::Back,
+                (front, count) if front == count => EdgeFacing::Front,
+                _ => EdgeFacing::Silhouette,
+            }
+        })
+        .collect();
+}
+
+// returns how many edges were inside the view, whether or not they were then culled
+pub fn draw<T: LineTarget>(shape: &Shape3d, target: &mut T, viewport: &Viewport, projection: &Projection, mode: CullMode, depth_cue: &DepthCue) -> Result<usize, String> {
+    shape.draw_axes(target, viewport, projection)?;
+    let facings = edge_facings(shape, projection);
+    let projected_edges = shape.projected_edges(projection, viewport)?;
+    let inside = projected_edges.len();
+    for (edge_index, start, end) in projected_edges {
+        let colors = shape.style().edge_colors(edge_index, &shape.edges()[edge_index]);
+        match (facings[edge_index], mode) {
+            (EdgeFacing::Back, CullMode::BackFaces | CullMode::Silhouettes) => {}
+            (EdgeFacing::Silhouette, CullMode::Silhouettes) => {
+                // drawn wider and solid so the outline stands out from the front edges
+                let stroke = Stroke { width: shape.stroke().width + SILHOUETTE_EXTRA_WIDTH, ..shape.stroke() };
+                style::draw_edge(target, &start, &end, (SILHOUETTE_COLOR, SILHOUETTE_COLOR), style::LinePattern::Solid, &stroke, depth_cue)?;
+            }
+            _ => style::draw_edge(target, &start, &end, colors, shape.style().pattern, &shape.stroke(), depth_cue)?,
+        }
+    }
+    return Ok(inside);
+}
@@ -0,0 +1,83 @@
+use sdl2::pixels::Color;
+
+use crate::application::{projection::Projection, bounds::BoundingSphere};
+
+#[derive(Debug)]
+pub struct DepthCue {
+    enabled: bool,
+    near_color: Color,
+    far_color: Color,
+    range: Option<(f64, f64)>, // depths that get the near and far colors, None fits them to the shapes in view every frame
+    near: f64,
+    far: f64,
+}
+
+impl DepthCue {
+    pub fn default() -> Self {
+        return Self {
+            enabled: false,
+            near_color: Color::WHITE,
+            far_color: Color::RGB(40, 40, 70),
+            range: None,
+            near: 0.0,
+            far: 1.0,
+        };
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        return self.enabled;
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn set_colors(&mut self, near_color: Color, far_color: Color) {
+        self.near_color = near_color;
+        self.far_color = far_color;
+    }
+
+    pub fn set_range(&mut self, range: Option<(f64, f64)>) {
+        self.range = range;
+        if let Some((near, far)) = range {
+            self.near = near;
+            self.far = far;
+        }
+    }
+
+    // depth is measured along the view direction, which is +z for every projection. the range is taken from the
+    // bounding spheres of the shapes in view, which can reach a little past their nearest and farthest vertices
+    pub fn fit<'a>(&mut self, spheres: impl IntoIterator<Item = &'a BoundingSphere>, projection: &Projection) {
+        if self.range.is_some() {
+            return;
+        }
+        let (near, far) = spheres.into_iter().fold((f64::MAX, f64::MIN), |(near, far), sphere| {
+            let depth = projection.to_view(&sphere.center).z;
+            (near.min(depth - sphere.radius), far.max(depth + sphere.radius))
+        });
+        if near <= far {
+            self.near = near;
+            self.far = far;
+        }
+    }
+
+    // 0 at the near depth up to 1 at the far depth, whether or not cueing is enabled
+    pub fn fraction(&self, depth: f64) -> f64 {
+        return if self.far > self.near { ((depth - self.near) / (self.far - self.near)).clamp(0.0, 1.0) } else { 0.0 };
+    }
+
+    // the edge's own color tinted by the cue color for its depth
+    pub fn shade(&self, color: Color, depth: f64) -> Color {
+        if !self.enabled {
+            return color;
+        }
+        let t = self.fraction(depth);
+        let cue = |near: u8, far: u8| near as f64 + (far as f64 - near as f64) * t;
+        let tint = |channel: u8, cue: f64| (channel as f64 * cue / 255.0).round() as u8;
+        return Color::RGB(
+            tint(color.r, cue(self.near_color.r, self.far_color.r)),
+            tint(color.g, cue(self.near_color.g, self.far_color.g)),
+            tint(color.b, cue(self.near_color.b, self.far_color.b)),
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, projection::Projection, depth_cue::DepthCue, marker::Markers, line::{self, Stroke, LineTarget}, viewport::Viewport};
+
+// an RGB24 image in memory, for rendering without a window
+pub struct Framebuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        return Self { width, height, pixels: vec![0; (width * height * 3) as usize] };
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        return (self.width, self.height);
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        return &self.pixels;
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [u8] {
+        return &mut self.pixels;
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        for pixel in self.pixels.chunks_exact_mut(3) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b]);
+        }
+    }
+
+    // mixes the color into the pixel by coverage, pixels outside the buffer are ignored
+    pub fn blend(&mut self, x: i32, y: i32, color: Color, coverage: f64) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+        let alpha = coverage.clamp(0.0, 1.0) * color.a as f64 / 255.0;
+        let index = ((y as u32 * self.width + x as u32) * 3) as usize;
+        for (channel, source) in self.pixels[index..index + 3].iter_mut().zip([color.r, color.g, color.b]) {
+            *channel = (*channel as f64 + (source as f64 - *channel as f64) * alpha).round() as u8;
+        }
+    }
+
+    // start and end are projected points, as from Projection::project
+    pub fn draw_line(&mut self, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) {
+        let size = self.size();
+        line::rasterize(start.truncate(), end.truncate(), stroke, size, &mut |x, y, coverage| {
+            self.blend(x, y, color, coverage);
+        });
+    }
+
+    pub fn draw_shape(&mut self, shape: &Shape3d, projection: &Projection) -> Result<(), String> {
+        let viewport = Viewport::new((0, 0), self.size(), 1.0);
+        let depth_cue = DepthCue::default();
+        shape.draw(self, &viewport, projection, &depth_cue)?;
+        // shapes without edges, like point clouds, are drawn as dots
+        let markers = Markers::default();
+        if markers.draws_markers(shape) {
+            markers.draw(self, shape, &viewport, projection, &depth_cue)?;
+        }
+        return Ok(());
+    }
+}
+
+impl LineTarget for Framebuffer {
+    fn stroke_line(&mut self, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) -> Result<(), String> {
+        self.draw_line(start, end, color, stroke);
+        return Ok(());
+    }
+}
@@ -0,0 +1,103 @@
+use glam::DVec3;
+
+use crate::application::{projection::{self, Projection, Lens}, viewport::Viewport, bounds::BoundingSphere};
+
+const MARGIN: f64 = 4.0; // pixels around the viewport still counted as inside, so thick lines and caps are not cut short
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Containment {
+    Outside,
+    Intersecting,
+    Inside,
+}
+
+// the part of world space a projection shows in a viewport, as planes with the inside on their positive side
+#[derive(Debug)]
+pub struct Frustum {
+    planes: Vec<(DVec3, f64)>, // unit normal and offset, a point is inside a plane when normal.dot(point) + offset >= 0
+}
+
+impl Frustum {
+    pub fn new(projection: &Projection, viewport: &Viewport) -> Self {
+        let center = viewport.center();
+        let (origin, size) = (viewport.origin(), viewport.size());
+        // screen distances from the projected world origin to each side of the viewport
+        let left = center.0 - origin.0 as f64 + MARGIN;
+        let right = origin.0 as f64 + size.0 as f64 - center.0 + MARGIN;
+        let top = center.1 - origin.1 as f64 + MARGIN;
+        let bottom = origin.1 as f64 + size.1 as f64 - center.1 + MARGIN;
+        let planes = match projection.lens {
+            Lens::Orthographic { scale } => {
+                let scale = scale * viewport.pixel_scale();
+                vec![
+                    (DVec3::X, left / scale),
+                    (DVec3::NEG_X, right / scale),
+                    (DVec3::Y, top / scale),
+                    (DVec3::NEG_Y, bottom / scale),
+                ]
+            }
+            Lens::Perspective { distance } => {
+                // x * distance * pixel_scale / (z + distance) has to stay between -left and right, and likewise for y
+                let focal = distance * viewport.pixel_scale();
+                vec![
+                    (DVec3::new(focal, 0.0, left), left * distance),
+                    (DVec3::new(-focal, 0.0, right), right * distance),
+                    (DVec3::new(0.0, focal, top), top * distance),
+                    (DVec3::new(0.0, -focal, bottom), bottom * distance),
+                    (DVec3::Z, distance - projection::NEAR),
+                ]
+            }
+        };
+        // the planes above are in view space, they are turned and moved into world space so points can be tested as they are
+        let world_origin = projection.to_view(&DVec3::ZERO);
+        let planes = planes.into_iter()
+            .map(|(normal, offset)| {
+                let (normal, offset) = (normal / normal.length(), offset / normal.length());
+                (projection.from_view_direction(&normal), offset + normal.dot(world_origin))
+            })
+            .collect();
+        return Self { planes };
+    }
+
+    pub fn contains_sphere(&self, sphere: &BoundingSphere) -> Containment {
+        let mut containment = Containment::Inside;
+        for (normal, offset) in self.planes.iter() {
+            let distance = normal.dot(sphere.center) + offset;
+            if distance < -sphere.radius {
+                return Containment::Outside;
+            }
+            if distance < sphere.radius {
+                containment = Containment::Intersecting;
+            }
+        }
+        return containment;
+    }
+
+    // true when both ends are outside the same plane, so no part of the segment can be seen.
+    // segments crossing a corner outside can pass this test and are left to clipping
+    pub fn rejects_segment(&self, a: &DVec3, b: &DVec3) -> bool {
+        return self.planes.iter().any(|(normal, offset)| normal.dot(*a) + offset < 0.0 && normal.dot(*b) + offset < 0.0);
+    }
+}
+
+// what frustum culling skipped in the last frame, summed over every view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CullingStats {
+    pub shapes_drawn: usize,
+    pub shapes_culled: usize,
+    pub edges_drawn: usize,
+    pub edges_rejected: usize,
+}
+
+impl CullingStats {
+    pub fn default() -> Self {
+        return Self { shapes_drawn: 0, shapes_culled: 0, edges_drawn: 0, edges_rejected: 0 };
+    }
+
+    pub fn text(&self) -> String {
+        return format!(
+            "Shapes {} drawn {} culled, Edges {} drawn {} rejected",
+            self.shapes_drawn, self.shapes_culled, self.edges_drawn, self.edges_rejected
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, projection::Projection, viewport::Viewport, frustum::Frustum, depth_cue::DepthCue, line::LineTarget, style::{self, LinePattern}};
+
+const SAMPLE_SPACING: f64 = 4.0; // pixels between visibility tests along an edge
+const DEPTH_BIAS: f64 = 0.5; // world units a face has to be in front of an edge to hide it
+const HIDDEN_COLOR: Color = Color::RGB(90, 90, 90);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HiddenLineMode {
+    Off, // every edge is drawn solid
+    Hidden, // occluded parts of edges are not drawn
+    Dashed, // occluded parts of edges are drawn dashed in a dim color
+}
+
+impl HiddenLineMode {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            HiddenLineMode::Off => "Hidden Lines Off",
+            HiddenLineMode::Hidden => "Hidden Lines Removed",
+            HiddenLineMode::Dashed => "Hidden Lines Dashed",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            HiddenLineMode::Off => HiddenLineMode::Hidden,
+            HiddenLineMode::Hidden => HiddenLineMode::Dashed,
+            HiddenLineMode::Dashed => HiddenLineMode::Off,
+        };
+    }
+}
+
+// a face of some shape prepared for point occlusion tests in screen space
+struct Occluder {
+    shape_index: usize,
+    indices: Vec<usize>,
+    normal: DVec3,
+    point: DVec3,
+    screen: Vec<(f64, f64)>,
+    min: (f64, f64),
+    max: (f64, f64),
+}
+
+impl Occluder {
+    fn new(shape_index: usize, shape_vertices: &[DVec3], indices: &[usize], projection: &Projection, viewport: &Viewport) -> Option<Self> {
+        let vertices: Vec<DVec3> = indices.iter().map(|index| shape_vertices.get(*index).copied()).collect::<Option<_>>()?;
+        let mut normal = DVec3::ZERO;
+        for (index, vertex) in vertices.iter().enumerate() {
+            normal += vertex.cross(vertices[(index + 1) % vertices.len()]);
+        }
+        if normal.length() == 0.0 {
+            return None;
+        }
+        // a face crossing the near plane cannot be projected as a whole so it does not hide anything
+        let mut screen = vec![];
+        for vertex in vertices.iter() {
+            let projected = projection.project(vertex, viewport)?;
+            screen.push((projected.x, projected.y));
+        }
+        let min = screen.iter().fold((f64::MAX, f64::MAX), |min, point| (min.0.min(point.0), min.1.min(point.1)));
+        let max = screen.iter().fold((f64::MIN, f64::MIN), |max, point| (max.0.max(point.0), max.1.max(point.1)));
+        return Some(Self { shape_index, indices: indices.to_vec(), normal: normal.normalize(), point: vertices[0], screen, min, max });
+    }
+
+    fn contains_edge(&self, shape_index: usize, edge: &[usize; 2]) -> bool {
+        return self.shape_index == shape_index && self.indices.contains(&edge[0]) && self.indices.contains(&edge[1]);
+    }
+
+    // crossing number test, works for any simple polygon
+    fn covers(&self, x: f64, y: f64) -> bool {
+        if x < self.min.0 || x > self.max.0 || y < self.min.1 || y > self.max.1 {
+            return false;
+        }
+        let mut inside = false;
+        for index in 0..self.screen.len() {
+            let (ax, ay) = self.screen[index];
+            let (bx, by) = self.screen[(index + 1) % self.screen.len()];
+            if (ay > y) != (by > y) && x < ax + (y - ay) * (bx - ax) / (by - ay) {
+                inside = !inside;
+            }
+        }
+        return inside;
+    }
+
+    fn hides(&self, point: &DVec3, projection: &Projection, viewport: &Viewport) -> bool {
+        let screen = match projection.project(point, viewport) {
+            Some(screen) => screen,
+            None => return false,
+        };
+        if !self.covers(screen.x, screen.y) {
+            return false;
+        }
+        // walk from the point back towards the eye and see if the face plane is crossed on the way
+        let direction = projection.view_direction(point);
+        let facing = self.normal.dot(direction);
+        if facing.abs() < 1e-12 {
+            return false;
+        }
+        let distance = self.normal.dot(*point - self.point) / facing;
+        return distance > DEPTH_BIAS && distance < projection.eye_distance(point);
+    }
+}
+
+pub struct HiddenLines {
+    mode: HiddenLineMode,
+}
+
+impl HiddenLines {
+    pub fn default() -> Self {
+        return Self { mode: HiddenLineMode::Off };
+    }
+
+    pub fn mode(&self) -> HiddenLineMode {
+        return self.mode;
+    }
+
+    pub fn set_mode(&mut self, mode: HiddenLineMode) {
+        self.mode = mode;
+    }
+
+    // shapes that are not visible are left out entirely, as they are outside the view they cannot hide anything in it.
+    // shapes without faces, like point clouds and plain edge lists, have nothing to hide other edges with, their own
+    // edges are still hidden by the faces of other shapes. returns how many edges were inside the view
+    pub fn draw<T: LineTarget>(&self, target: &mut T, viewport: &Viewport, shapes: &[Shape3d], visible: &[bool], projection: &Projection, depth_cue: &DepthCue) -> Result<usize, String> {
+        let frustum = Frustum::new(projection, viewport);
+        let visible_shapes = || shapes.iter().enumerate().filter(|(shape_index, _)| visible.get(*shape_index).copied().unwrap_or(true));
+
+        let vertices: Vec<&[DVec3]> = shapes.iter().map(|shape| shape.vertices()).collect();
+        let mut occluders: Vec<Occluder> = vec![];
+        for (shape_index, shape) in visible_shapes() {
+            for face in shape.faces().iter() {
+                if let Some(occluder) = Occluder::new(shape_index, vertices[shape_index], face, projection, viewport) {
+                    occluders.push(occluder);
+                }
+            }
+        }
+
+        let mut inside = 0;
+        for (shape_index, shape) in visible_shapes() {
+            for (edge_index, edge) in shape.edges().iter().enumerate() {
+                let (vertex_a, vertex_b) = match (vertices[shape_index].get(edge[0]), vertices[shape_index].get(edge[1])) {
+                    (Some(vertex_a), Some(vertex_b)) => (vertex_a, vertex_b),
+                    _ => return Err(format!("missing at least one vertex of edge number {edge:?}")),
+                };
+                if frustum.rejects_segment(vertex_a, vertex_b) {
+                    continue;
+                }
+                let (start, end) = match projection.clip_segment(vertex_a, vertex_b) {
+                    Some(segment) => segment,
+                    None => continue,
+                };
+                let screen_length = match projection.project_segment(&start, &end, viewport) {
+                    Some((screen_start, screen_end)) => screen_start.truncate().distance(screen_end.truncate()),
+                    None => continue,
+                };
+                inside += 1;
+
+                // each piece of the edge is classified by its midpoint, then runs of equally visible pieces are drawn together
+                let pieces = (screen_length / SAMPLE_SPACING).ceil().max(1.0) as usize;
+                let hidden: Vec<bool> = (0..pieces)
+                    .map(|piece| {
+                        let midpoint = start.lerp(end, (piece as f64 + 0.5) / pieces as f64);
+                        occluders.iter()
+                            .filter(|occluder| !occluder.contains_edge(shape_index, edge))
+                            .any(|occluder| occluder.hides(&midpoint, projection, viewport))
+                    })
+                    .collect();
+                let colors = shape.style().edge_colors(edge_index, edge);
+                let mut run_start = 0;
+                for piece in 1..=pieces {
+                    if piece < pieces && hidden[piece] == hidden[run_start] {
+                        continue;
+                    }
+                    let t0 = run_start as f64 / pieces as f64;
+                    let t1 = piece as f64 / pieces as f64;
+                    let (run_colors, pattern) = match (hidden[run_start], self.mode) {
+                        (false, _) => ((style::lerp_color(colors.0, colors.1, t0), style::lerp_color(colors.0, colors.1, t1)), shape.style().pattern),
+                        (true, HiddenLineMode::Dashed) => ((HIDDEN_COLOR, HIDDEN_COLOR), LinePattern::Dashed),
+                        _ => {
+                            run_start = piece;
+                            continue;
+                        }
+                    };
+                    if let Some((run_start_point, run_end_point)) = projection.project_segment(&start.lerp(end, t0), &start.lerp(end, t1), viewport) {
+                        style::draw_edge(target, &run_start_point, &run_end_point, run_colors, pattern, &shape.stroke(), depth_cue)?;
+                    }
+                    run_start = piece;
+                }
+            }
+        }
+        return Ok(inside);
+    }
+}
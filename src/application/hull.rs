@@ -0,0 +1,60 @@
+use glam::DVec3;
+
+// vertices closer than this fraction of the shape's extent to a face plane lie on that face
+const TOLERANCE: f64 = 1e-6;
+
+// the faces of the convex hull as lists of vertex indices, counter-clockwise seen from outside.
+// coplanar vertices make up one polygon, vertices inside the hull are left out
+pub fn convex_hull_faces(vertices: &[DVec3]) -> Vec<Vec<usize>> {
+    let mut faces: Vec<Vec<usize>> = vec![];
+    if vertices.len() < 4 {
+        return faces;
+    }
+    let centroid = vertices.iter().copied().sum::<DVec3>() / vertices.len() as f64;
+    let extent = vertices.iter().map(|vertex| vertex.distance(centroid)).fold(0.0, f64::max);
+    let tolerance = TOLERANCE * extent.max(1.0);
+
+    let count = vertices.len();
+    for i in 0..count {
+        for j in (i + 1)..count {
+            for k in (j + 1)..count {
+                let normal = (vertices[j] - vertices[i]).cross(vertices[k] - vertices[i]);
+                if normal.length() <= tolerance * tolerance {
+                    continue; // collinear
+                }
+                let mut normal = normal.normalize();
+                let distances: Vec<f64> = vertices.iter().map(|vertex| normal.dot(*vertex - vertices[i])).collect();
+                if distances.iter().all(|distance| *distance <= tolerance) {
+                    // every vertex is behind the plane so the normal already points outwards
+                } else if distances.iter().all(|distance| *distance >= -tolerance) {
+                    normal = -normal;
+                } else {
+                    continue;
+                }
+                let mut face: Vec<usize> = (0..count).filter(|index| distances[*index].abs() <= tolerance).collect();
+                if faces.iter().any(|existing| {
+                    let mut existing = existing.clone();
+                    existing.sort();
+                    existing == face
+                }) {
+                    continue;
+                }
+                wind_counter_clockwise(vertices, &mut face, normal);
+                faces.push(face);
+            }
+        }
+    }
+    return faces;
+}
+
+// sorts the vertices of a flat convex polygon counter-clockwise around the normal
+pub fn wind_counter_clockwise(vertices: &[DVec3], face: &mut [usize], normal: DVec3) {
+    let center = face.iter().map(|index| vertices[*index]).sum::<DVec3>() / face.len() as f64;
+    let u = (vertices[face[0]] - center).normalize();
+    let v = normal.cross(u);
+    let angle = |index: &usize| {
+        let offset = vertices[*index] - center;
+        return offset.dot(v).atan2(offset.dot(u));
+    };
+    face.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+}
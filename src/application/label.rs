@@ -0,0 +1,118 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use glam::DVec3;
+use sdl2::{render::{Canvas, Texture, TextureCreator}, video::{Window, WindowContext}, rect::Rect, pixels::Color, ttf::{Font, Sdl2TtfContext}};
+
+use crate::application::{projection::{self, Projection}, viewport::Viewport};
+
+// fonts are tried in order, the first one that loads is used for every label
+const FONT_PATHS: [&str; 5] = [
+    "C:\\Windows\\Fonts\\consola.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf",
+    "/System/Library/Fonts/Menlo.ttc",
+    "/Library/Fonts/Arial.ttf",
+];
+const FONT_SIZE: u16 = 14;
+
+struct Glyph {
+    texture: Option<Texture<'static>>, // None for characters without any pixels, like spaces
+    advance: i32, // pixels to the next character
+}
+
+// the label font and every character it has drawn so far. each character is rendered and uploaded only once, in white,
+// and tinted to the label's color when it is copied, so thousands of vertex labels cost no more than their copies
+pub struct LabelFont {
+    font: Font<'static, 'static>,
+    texture_creator: &'static TextureCreator<WindowContext>,
+    glyphs: HashMap<char, Glyph>,
+}
+
+impl LabelFont {
+    pub fn new(font: Font<'static, 'static>, texture_creator: &'static TextureCreator<WindowContext>) -> Self {
+        return Self { font, texture_creator, glyphs: HashMap::new() };
+    }
+
+    fn glyph(&mut self, character: char) -> Result<&mut Glyph, String> {
+        return match self.glyphs.entry(character) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let texture = match self.font.render_char(character).blended(Color::WHITE) {
+                    Ok(surface) => Some(self.texture_creator.create_texture_from_surface(&surface).map_err(|e| e.to_string())?),
+                    Err(_) => None,
+                };
+                let advance = match (self.font.find_glyph_metrics(character), &texture) {
+                    (Some(metrics), _) => metrics.advance,
+                    (None, Some(texture)) => texture.query().width as i32,
+                    (None, None) => 0,
+                };
+                Ok(entry.insert(Glyph { texture, advance }))
+            }
+        };
+    }
+}
+
+#[derive(Debug)]
+pub struct Label {
+    text: String,
+    location: DVec3,
+    color: Color,
+}
+
+impl Label {
+    pub fn new(text: &str, location: DVec3, color: Color) -> Self {
+        return Self { text: text.to_string(), location, color };
+    }
+
+    pub fn text(&self) -> &str {
+        return &self.text;
+    }
+
+    pub fn location(&self) -> DVec3 {
+        return self.location;
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas<Window>, font: &mut LabelFont, viewport: &Viewport, projection: &Projection) -> Result<(), String> {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        let anchor = match projection.project(&self.location, viewport) {
+            Some(screen) => projection::to_point(&screen),
+            None => return Ok(()),
+        };
+        // labels of points outside the view are left out, there can be one for every vertex of a point cloud
+        if !viewport.rect().contains_point(anchor) {
+            return Ok(());
+        }
+
+        // sit just above and to the right of the anchor so the point itself stays visible
+        let mut x = anchor.x + 4;
+        let y = anchor.y - font.font.height() - 2;
+        for character in self.text.chars() {
+            let glyph = font.glyph(character)?;
+            if let Some(texture) = &mut glyph.texture {
+                texture.set_color_mod(self.color.r, self.color.g, self.color.b);
+                texture.set_alpha_mod(self.color.a);
+                let query = texture.query();
+                canvas.copy(texture, None, Rect::new(x, y, query.width, query.height))?;
+            }
+            x += glyph.advance;
+        }
+        return Ok(());
+    }
+}
+
+// the font named by WIRE_FRAME_FONT if it is set, otherwise the first of the common system fonts that loads.
+// None leaves the viewer running without labels
+pub fn load_font(ttf: &'static Sdl2TtfContext) -> Option<Font<'static, 'static>> {
+    let mut paths: Vec<String> = FONT_PATHS.iter().map(|path| path.to_string()).collect();
+    if let Ok(path) = std::env::var("WIRE_FRAME_FONT") {
+        paths.insert(0, path);
+    }
+    for path in paths.iter() {
+        if let Ok(font) = ttf.load_font(path, FONT_SIZE) {
+            return Some(font);
+        }
+    }
+    return None;
+}
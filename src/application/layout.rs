@@ -0,0 +1,83 @@
+use glam::{DVec3, DQuat};
+use crate::application::{projection::{Projection, Lens}, viewport::Viewport};
+
+// a camera looking at the world origin, the scene is turned by its orientation as it is projected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct View {
+    pub name: &'static str,
+    pub orientation: DQuat,
+    pub lens: Option<Lens>, // None follows the lens picked with the projection key
+}
+
+impl View {
+    // world y points down the screen, so the top view looks along +y and the side view looks along -x
+    pub fn quad() -> [View; 4] {
+        return [
+            View { name: "Top", orientation: DQuat::from_rotation_x(std::f64::consts::FRAC_PI_2), lens: Some(Lens::Orthographic { scale: 1.0 }) },
+            View { name: "Front", orientation: DQuat::IDENTITY, lens: None },
+            View { name: "Side", orientation: DQuat::from_rotation_y(std::f64::consts::FRAC_PI_2), lens: Some(Lens::Orthographic { scale: 1.0 }) },
+            View {
+                name: "Perspective",
+                orientation: DQuat::from_rotation_x(-0.45) * DQuat::from_rotation_y(0.6),
+                lens: Some(Lens::Perspective { distance: 800.0 }),
+            },
+        ];
+    }
+
+    // how the view sees the world when it looks at target, zoomed to scale pixels per world unit
+    pub fn projection(&self, default: Lens, scale: f64, target: &DVec3) -> Projection {
+        return Projection::new(self.lens.unwrap_or(default).with_scale(scale)).with_view(self.orientation, *target);
+    }
+}
+
+#[derive(Debug)]
+pub struct Layout {
+    views: [View; 4],
+    split: bool, // all four views at once, otherwise only the maximized one
+    maximized: usize,
+}
+
+impl Layout {
+    // starts out as the single front view the viewer always had
+    pub fn default() -> Self {
+        return Self { views: View::quad(), split: false, maximized: 1 };
+    }
+
+    pub fn is_split(&self) -> bool {
+        return self.split;
+    }
+
+    pub fn toggle_split(&mut self) {
+        self.split = !self.split;
+    }
+
+    pub fn views(&self) -> &[View; 4] {
+        return &self.views;
+    }
+
+    // shows the view on its own, out of range indices are ignored
+    pub fn maximize(&mut self, index: usize) {
+        if index < self.views.len() {
+            self.maximized = index;
+            self.split = false;
+        }
+    }
+
+    // the views to draw and the part of the whole viewport each one gets
+    pub fn viewports(&self, viewport: &Viewport) -> Vec<(View, Viewport)> {
+        if !self.split {
+            return vec![(self.views[self.maximized], *viewport)];
+        }
+        let size = viewport.size();
+        let (half_width, half_height) = (size.0 / 2, size.1 / 2);
+        return self.views.iter().enumerate()
+            .map(|(index, view)| {
+                let x = if index % 2 == 0 { 0 } else { half_width };
+                let y = if index < 2 { 0 } else { half_height };
+                let width = if index % 2 == 0 { half_width } else { size.0 - half_width };
+                let height = if index < 2 { half_height } else { size.1 - half_height };
+                (*view, viewport.sub_viewport((x as i32, y as i32), (width, height)))
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+
+use glam::{DVec2, DVec3};
+use sdl2::{render::{Canvas, BlendMode}, video::Window, rect::Point, pixels::Color};
+
+use crate::application::projection;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineCap {
+    Butt, // stops at the end points
+    Square, // extends past the end points by half the width
+    Round, // half disc around the end points
+}
+
+impl LineCap {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            LineCap::Butt => "Butt",
+            LineCap::Square => "Square",
+            LineCap::Round => "Round",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            LineCap::Butt => LineCap::Square,
+            LineCap::Square => LineCap::Round,
+            LineCap::Round => LineCap::Butt,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    pub width: f64, // pixels
+    pub antialiased: bool,
+    pub cap: LineCap,
+}
+
+impl Stroke {
+    pub fn default() -> Self {
+        return Self { width: 1.0, antialiased: false, cap: LineCap::Butt };
+    }
+
+    // a plain one pixel line that the renderer can draw by itself
+    pub fn is_hairline(&self) -> bool {
+        return self.width <= 1.0 && !self.antialiased;
+    }
+}
+
+// calls plot with the coverage from 0 to 1 of every pixel the stroked segment touches inside a target of the given size.
+// pixel centers are at integer coordinates, like the canvas draws lines
+pub fn rasterize(start: DVec2, end: DVec2, stroke: &Stroke, size: (u32, u32), plot: &mut impl FnMut(i32, i32, f64)) {
+    if !start.is_finite() || !end.is_finite() {
+        return;
+    }
+    // the margin keeps caps and antialiased fringes of lines that end just off the target
+    let margin = DVec2::splat(stroke.width + 2.0);
+    let (start, end) = match clip(start, end, -margin, DVec2::new(size.0 as f64, size.1 as f64) + margin) {
+        Some(segment) => segment,
+        None => return,
+    };
+    if stroke.width <= 1.0 {
+        if stroke.antialiased {
+            wu(start, end, plot);
+        } else {
+            bresenham(start, end, plot);
+        }
+    } else {
+        thick(start, end, stroke, plot);
+    }
+}
+
+// liang-barsky against an axis aligned rectangle
+fn clip(start: DVec2, end: DVec2, min: DVec2, max: DVec2) -> Option<(DVec2, DVec2)> {
+    let delta = end - start;
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for (p, q) in [
+        (-delta.x, start.x - min.x),
+        (delta.x, max.x - start.x),
+        (-delta.y, start.y - min.y),
+        (delta.y, max.y - start.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    return Some((start + delta * t0, start + delta * t1));
+}
+
+fn bresenham(start: DVec2, end: DVec2, plot: &mut impl FnMut(i32, i32, f64)) {
+    let (mut x, mut y) = (start.x.round() as i32, start.y.round() as i32);
+    let (x1, y1) = (end.x.round() as i32, end.y.round() as i32);
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let step_x = if x < x1 { 1 } else { -1 };
+    let step_y = if y < y1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    loop {
+        plot(x, y, 1.0);
+        if x == x1 && y == y1 {
+            return;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+// xiaolin wu's line, each column (or row for steep lines) shares its coverage between two pixels
+fn wu(start: DVec2, end: DVec2, plot: &mut impl FnMut(i32, i32, f64)) {
+    let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
+    let (mut a, mut b) = if steep { (DVec2::new(start.y, start.x), DVec2::new(end.y, end.x)) } else { (start, end) };
+    if a.x > b.x {
+        std::mem::swap(&mut a, &mut b);
+    }
+    let mut plot = |x: f64, y: f64, coverage: f64| {
+        if steep { plot(y as i32, x as i32, coverage) } else { plot(x as i32, y as i32, coverage) }
+    };
+    let fraction = |value: f64| value - value.floor();
+    let gradient = if b.x - a.x == 0.0 { 1.0 } else { (b.y - a.y) / (b.x - a.x) };
+
+    let x_start = a.x.round();
+    let y_start = a.y + gradient * (x_start - a.x);
+    let gap = 1.0 - fraction(a.x + 0.5);
+    plot(x_start, y_start.floor(), (1.0 - fraction(y_start)) * gap);
+    plot(x_start, y_start.floor() + 1.0, fraction(y_start) * gap);
+
+    let x_end = b.x.round();
+    let y_end = b.y + gradient * (x_end - b.x);
+    let gap = fraction(b.x + 0.5);
+    plot(x_end, y_end.floor(), (1.0 - fraction(y_end)) * gap);
+    plot(x_end, y_end.floor() + 1.0, fraction(y_end) * gap);
+
+    let mut y = y_start + gradient;
+    let mut x = x_start + 1.0;
+    while x < x_end {
+        plot(x, y.floor(), 1.0 - fraction(y));
+        plot(x, y.floor() + 1.0, fraction(y));
+        y += gradient;
+        x += 1.0;
+    }
+}
+
+// coverage from the signed distance of each pixel center to the outline of the stroke
+fn thick(start: DVec2, end: DVec2, stroke: &Stroke, plot: &mut impl FnMut(i32, i32, f64)) {
+    let half_width = stroke.width / 2.0;
+    let direction = (end - start).try_normalize().unwrap_or(DVec2::X);
+    let (start, end) = match stroke.cap {
+        LineCap::Square => (start - direction * half_width, end + direction * half_width),
+        LineCap::Butt | LineCap::Round => (start, end),
+    };
+    let length = start.distance(end);
+    // walks along the major axis and only looks at the pixels that are close enough to the segment to be touched,
+    // including the antialiased fringe. across the major axis that reach grows with the slope, by at most a factor of sqrt 2
+    let reach = half_width + 1.0;
+    let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
+    let major = |point: DVec2| if steep { point.y } else { point.x };
+    let minor = |point: DVec2| if steep { point.x } else { point.y };
+    let slope = if major(end) == major(start) { 0.0 } else { (minor(end) - minor(start)) / (major(end) - major(start)) };
+    let span = reach * (1.0 + slope * slope).sqrt();
+    let (low, high) = (major(start).min(major(end)), major(start).max(major(end)));
+    for u in ((low - reach).floor() as i32)..=((high + reach).ceil() as i32) {
+        // past the end points the caps stay around the end point's minor coordinate
+        let center = minor(start) + ((u as f64).clamp(low, high) - major(start)) * slope;
+        for v in ((center - span).floor() as i32)..=((center + span).ceil() as i32) {
+            let (x, y) = if steep { (v, u) } else { (u, v) };
+            let offset = DVec2::new(x as f64, y as f64) - start;
+            let along = offset.dot(direction);
+            let outside = match stroke.cap {
+                LineCap::Round => offset.distance(direction * along.clamp(0.0, length)) - half_width,
+                LineCap::Butt | LineCap::Square => (offset.perp_dot(direction).abs() - half_width).max(-along).max(along - length),
+            };
+            let coverage = if stroke.antialiased {
+                (0.5 - outside).clamp(0.0, 1.0)
+            } else if outside <= 0.0 {
+                1.0
+            } else {
+                0.0
+            };
+            if coverage > 0.0 {
+                plot(x, y, coverage);
+            }
+        }
+    }
+}
+
+// anything projected segments can be drawn on
+pub trait LineTarget {
+    fn stroke_line(&mut self, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) -> Result<(), String>;
+}
+
+impl LineTarget for Canvas<Window> {
+    fn stroke_line(&mut self, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) -> Result<(), String> {
+        return draw(self, start, end, color, stroke);
+    }
+}
+
+// draws a projected segment on the canvas, blending partially covered pixels
+pub fn draw(canvas: &mut Canvas<Window>, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) -> Result<(), String> {
+    canvas.set_draw_color(color);
+    if stroke.is_hairline() {
+        return canvas.draw_line(projection::to_point(start), projection::to_point(end));
+    }
+    let size = canvas.output_size()?;
+    let mut result = Ok(());
+    canvas.set_blend_mode(BlendMode::Blend);
+    rasterize(start.truncate(), end.truncate(), stroke, size, &mut |x, y, coverage| {
+        if result.is_ok() {
+            canvas.set_draw_color(Color::RGBA(color.r, color.g, color.b, (color.a as f64 * coverage).round() as u8));
+            result = canvas.draw_point(Point::new(x, y));
+        }
+    });
+    canvas.set_blend_mode(BlendMode::None);
+    return result;
+}
+
+// colors closer than this in every channel are drawn as one, so gradients and depth cueing, which give every few pixels
+// of an edge a color of its own, still end up in a few dozen runs
+const COLOR_STEP: u16 = 8;
+
+// collects a frame's stroked segments so they reach the renderer in a few calls instead of one call or more per segment.
+// the segments of a layer are sorted into one run per color, drawn in the order the colors were first used, so within a
+// layer a segment can end up under one that was stroked before it. layers are drawn in order, callers start a new one
+// where what comes next has to cover what came before, like the axes and bounds on top of the shapes
+pub struct LineBatch {
+    size: (u32, u32),
+    runs: Vec<Run>,
+    layer_runs: HashMap<Color, usize>, // the runs of the current layer by color
+}
+
+struct Run {
+    color: Color,
+    hairlines: Vec<(Point, Point)>, // left to the renderer
+    points: HashMap<u8, Vec<Point>>, // pixels of wide and antialiased segments by alpha
+}
+
+impl LineBatch {
+    // size of the target the lines will be drawn on, pixels outside it are dropped
+    pub fn new(size: (u32, u32)) -> Self {
+        return Self { size, runs: vec![], layer_runs: HashMap::new() };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.runs.is_empty();
+    }
+
+    // pixels waiting to be drawn, hairlines count as their two end points
+    pub fn point_count(&self) -> usize {
+        return self.runs.iter()
+            .map(|run| run.hairlines.len() * 2 + run.points.values().map(|points| points.len()).sum::<usize>())
+            .sum();
+    }
+
+    // everything stroked from now on is drawn over everything stroked so far
+    pub fn next_layer(&mut self) {
+        self.layer_runs.clear();
+    }
+
+    fn run(&mut self, color: Color) -> &mut Run {
+        let step = |channel: u8| ((channel as u16 + COLOR_STEP / 2) / COLOR_STEP * COLOR_STEP).min(255) as u8;
+        let color = Color::RGBA(step(color.r), step(color.g), step(color.b), color.a);
+        let runs = &mut self.runs;
+        let index = *self.layer_runs.entry(color).or_insert_with(|| {
+            runs.push(Run { color, hairlines: vec![], points: HashMap::new() });
+            runs.len() - 1
+        });
+        return &mut self.runs[index];
+    }
+
+    // draws and empties the batch
+    pub fn submit(&mut self, canvas: &mut Canvas<Window>) -> Result<(), String> {
+        let mut result = Ok(());
+        for run in self.runs.drain(..) {
+            if result.is_err() {
+                break;
+            }
+            // hairlines are not blended, like draw leaves them, so the joints draw_lines touches twice do not come out darker
+            canvas.set_blend_mode(BlendMode::None);
+            canvas.set_draw_color(run.color);
+            for polyline in polylines(&run.hairlines) {
+                result = result.and_then(|_| canvas.draw_lines(polyline.as_slice()));
+            }
+            canvas.set_blend_mode(BlendMode::Blend);
+            for (alpha, points) in run.points.iter() {
+                canvas.set_draw_color(Color::RGBA(run.color.r, run.color.g, run.color.b, *alpha));
+                result = result.and_then(|_| canvas.draw_points(points.as_slice()));
+            }
+        }
+        self.runs.clear();
+        self.layer_runs.clear();
+        canvas.set_blend_mode(BlendMode::None);
+        return result;
+    }
+}
+
+// joins segments that share end points into as few connected polylines as it can, draw_lines draws one polyline per call
+fn polylines(segments: &[(Point, Point)]) -> Vec<Vec<Point>> {
+    let mut ends: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (index, (start, end)) in segments.iter().enumerate() {
+        ends.entry(*start).or_default().push(index);
+        ends.entry(*end).or_default().push(index);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut polylines = vec![];
+    for (index, (start, end)) in segments.iter().enumerate() {
+        if used[index] {
+            continue;
+        }
+        used[index] = true;
+        let mut polyline = vec![*start, *end];
+        // follow unused segments from the last point until there are none left there
+        while let Some(next) = ends.get_mut(polyline.last().unwrap()).and_then(|indices| {
+            while let Some(next) = indices.pop() {
+                if !used[next] {
+                    return Some(next);
+                }
+            }
+            return None;
+        }) {
+            used[next] = true;
+            let (next_start, next_end) = segments[next];
+            polyline.push(if next_start == *polyline.last().unwrap() { next_end } else { next_start });
+        }
+        polylines.push(polyline);
+    }
+    return polylines;
+}
+
+impl LineTarget for LineBatch {
+    fn stroke_line(&mut self, start: &DVec3, end: &DVec3, color: Color, stroke: &Stroke) -> Result<(), String> {
+        if color.a == 0 || !start.is_finite() || !end.is_finite() {
+            return Ok(());
+        }
+        let size = self.size;
+        if stroke.is_hairline() {
+            // clipped here as well, the end points of segments far off the target do not fit a Point
+            let margin = DVec2::splat(1.0);
+            if let Some((start, end)) = clip(start.truncate(), end.truncate(), -margin, DVec2::new(size.0 as f64, size.1 as f64) + margin) {
+                self.run(color).hairlines.push((projection::to_point(&start.extend(0.0)), projection::to_point(&end.extend(0.0))));
+            }
+            return Ok(());
+        }
+        let run = self.run(color);
+        rasterize(start.truncate(), end.truncate(), stroke, size, &mut |x, y, coverage| {
+            let alpha = (color.a as f64 * coverage).round() as u8;
+            if x < 0 || y < 0 || x >= size.0 as i32 || y >= size.1 as i32 || alpha == 0 {
+                return;
+            }
+            run.points.entry(alpha).or_default().push(Point::new(x, y));
+        });
+        return Ok(());
+    }
+}
@@ -0,0 +1,133 @@
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, projection::Projection, depth_cue::DepthCue, line::{Stroke, LineCap, LineTarget}, viewport::Viewport, parallel};
+
+const FAR_SCALE: f64 = 0.5; // size of the farthest markers relative to the nearest
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerMode {
+    Off, // only edges, shapes without edges still get markers so they do not vanish
+    Markers, // markers on top of the edges
+    PointCloud, // markers without edges
+}
+
+impl MarkerMode {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            MarkerMode::Off => "Vertex Markers Off",
+            MarkerMode::Markers => "Vertex Markers On",
+            MarkerMode::PointCloud => "Point Cloud",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            MarkerMode::Off => MarkerMode::Markers,
+            MarkerMode::Markers => MarkerMode::PointCloud,
+            MarkerMode::PointCloud => MarkerMode::Off,
+        };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MarkerShape {
+    Dot,
+    Square,
+    Cross,
+}
+
+impl MarkerShape {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            MarkerShape::Dot => "Dot",
+            MarkerShape::Square => "Square",
+            MarkerShape::Cross => "Cross",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            MarkerShape::Dot => MarkerShape::Square,
+            MarkerShape::Square => MarkerShape::Cross,
+            MarkerShape::Cross => MarkerShape::Dot,
+        };
+    }
+}
+
+#[derive(Debug)]
+pub struct Markers {
+    mode: MarkerMode,
+    shape: MarkerShape,
+    size: f64, // pixels across the nearest markers
+}
+
+impl Markers {
+    pub fn default() -> Self {
+        return Self { mode: MarkerMode::Off, shape: MarkerShape::Dot, size: 4.0 };
+    }
+
+    pub fn mode(&self) -> MarkerMode {
+        return self.mode;
+    }
+
+    pub fn set_mode(&mut self, mode: MarkerMode) {
+        self.mode = mode;
+    }
+
+    pub fn shape(&self) -> MarkerShape {
+        return self.shape;
+    }
+
+    pub fn set_shape(&mut self, shape: MarkerShape) {
+        self.shape = shape;
+    }
+
+    pub fn size(&self) -> f64 {
+        return self.size;
+    }
+
+    pub fn set_size(&mut self, size: f64) {
+        self.size = size.max(1.0);
+    }
+
+    // whether the shape's edges are drawn at all
+    pub fn draws_edges(&self) -> bool {
+        return self.mode != MarkerMode::PointCloud;
+    }
+
+    pub fn draws_markers(&self, shape: &Shape3d) -> bool {
+        return self.mode != MarkerMode::Off || shape.edges().is_empty();
+    }
+
+    // markers are colored like the shape's vertices and shrink with depth between the depth cue's near and far
+    pub fn draw<T: LineTarget>(&self, target: &mut T, shape: &Shape3d, viewport: &Viewport, projection: &Projection, depth_cue: &DepthCue) -> Result<(), String> {
+        for (index, point) in parallel::project(shape.vertices(), projection, viewport).iter().enumerate() {
+            let point = match point {
+                Some(point) => point,
+                None => continue,
+            };
+            let color = match &shape.style().vertex_colors {
+                Some(vertex_colors) => *vertex_colors.get(index).unwrap_or(&shape.style().color),
+                None => shape.style().color,
+            };
+            let size = self.size * viewport.pixel_scale() * (1.0 + (FAR_SCALE - 1.0) * depth_cue.fraction(point.z));
+            self.draw_marker(target, point, depth_cue.shade(color, point.z), size, shape.stroke().antialiased)?;
+        }
+        return Ok(());
+    }
+
+    // dots and squares are zero length strokes, their caps make the marker
+    fn draw_marker<T: LineTarget>(&self, target: &mut T, point: &DVec3, color: Color, size: f64, antialiased: bool) -> Result<(), String> {
+        let half = size / 2.0;
+        return match self.shape {
+            MarkerShape::Dot => target.stroke_line(point, point, color, &Stroke { width: size, antialiased, cap: LineCap::Round }),
+            MarkerShape::Square => target.stroke_line(point, point, color, &Stroke { width: size, antialiased, cap: LineCap::Square }),
+            MarkerShape::Cross => {
+                let stroke = Stroke { width: 1.0, antialiased, cap: LineCap::Butt };
+                target.stroke_line(&(*point - DVec3::new(half, half, 0.0)), &(*point + DVec3::new(half, half, 0.0)), color, &stroke)?;
+                target.stroke_line(&(*point - DVec3::new(half, -half, 0.0)), &(*point + DVec3::new(half, -half, 0.0)), color, &stroke)
+            }
+        };
+    }
+}
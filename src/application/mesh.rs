@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use glam::DVec3;
+
+use crate::application::bounds::BoundingSphere;
+
+// geometry in the shape's own coordinates, shared between every shape placed from it
+#[derive(Debug, Clone)]
+pub struct Mesh {
+    vertices: Vec<DVec3>,
+    edges: Vec<[usize; 2]>,
+    faces: Vec<Vec<usize>>, // counter-clockwise seen from outside, empty if the mesh has no face data
+    edge_faces: Vec<Vec<usize>>, // indices of the faces on either side of each edge, in the same order as edges
+    bounding_sphere: Option<BoundingSphere>, // None without vertices
+}
+
+impl Mesh {
+    pub fn new(vertices: Vec<DVec3>, edges: Vec<[usize; 2]>) -> Self {
+        let edge_faces = vec![vec![]; edges.len()];
+        let bounding_sphere = BoundingSphere::from_points(&vertices);
+        return Self { vertices, edges, faces: vec![], edge_faces, bounding_sphere };
+    }
+
+    // the edges are every side of every face, each shared side only once
+    pub fn with_faces<F: AsRef<[usize]>>(vertices: Vec<DVec3>, faces: Vec<F>) -> Self {
+        let faces: Vec<Vec<usize>> = faces.iter().map(|face| face.as_ref().to_vec()).collect();
+        let mut sides: HashMap<[usize; 2], Vec<usize>> = HashMap::new();
+        for (face_index, face) in faces.iter().enumerate() {
+            for side in sides_of(face) {
+                sides.entry(side).or_default().push(face_index);
+            }
+        }
+        let mut sides: Vec<([usize; 2], Vec<usize>)> = sides.into_iter().collect();
+        sides.sort();
+        let (edges, edge_faces) = sides.into_iter().unzip();
+        let bounding_sphere = BoundingSphere::from_points(&vertices);
+        return Self { vertices, edges, faces, edge_faces, bounding_sphere };
+    }
+
+    pub fn vertices(&self) -> &Vec<DVec3> {
+        return &self.vertices;
+    }
+
+    pub fn edges(&self) -> &Vec<[usize; 2]> {
+        return &self.edges;
+    }
+
+    pub fn faces(&self) -> &Vec<Vec<usize>> {
+        return &self.faces;
+    }
+
+    // keeps the current edges, use with_faces to derive them
+    pub fn set_faces(&mut self, faces: Vec<Vec<usize>>) {
+        let edge_indices: HashMap<[usize; 2], usize> = self.edges.iter()
+            .enumerate()
+            .map(|(edge_index, edge)| ([edge[0].min(edge[1]), edge[0].max(edge[1])], edge_index))
+            .collect();
+        self.edge_faces = vec![vec![]; self.edges.len()];
+        for (face_index, face) in faces.iter().enumerate() {
+            for side in sides_of(face) {
+                if let Some(edge_index) = edge_indices.get(&side) {
+                    self.edge_faces[*edge_index].push(face_index);
+                }
+            }
+        }
+        self.faces = faces;
+    }
+
+    // indices of the faces on either side of each edge, in the same order as edges()
+    pub fn edge_faces(&self) -> &Vec<Vec<usize>> {
+        return &self.edge_faces;
+    }
+
+    // in the mesh's own coordinates, found once as the vertices never change
+    pub fn bounding_sphere(&self) -> Option<BoundingSphere> {
+        return self.bounding_sphere;
+    }
+}
+
+// every side of a face with the lower vertex index first
+fn sides_of(face: &[usize]) -> impl Iterator<Item = [usize; 2]> + '_ {
+    return face.iter().enumerate().map(|(index, vertex)| {
+        let next = face[(index + 1) % face.len()];
+        return [(*vertex).min(next), (*vertex).max(next)];
+    });
+}
@@ -0,0 +1,213 @@
+use std::{error::Error, fs::File, io::{BufRead, BufReader}, path::Path};
+
+use glam::DVec3;
+
+use crate::application::shape3d::Shape3d;
+
+// a polygon mesh as read from a model file. the files have y up and faces counter-clockwise seen from outside.
+// world y points down the screen, so y is flipped and the faces are reversed as they are read
+#[derive(Debug)]
+pub struct Model {
+    vertices: Vec<DVec3>,
+    faces: Vec<Vec<usize>>,
+    lines: Vec<[usize; 2]>, // free standing edges, only used when the model has no faces
+}
+
+impl Model {
+    pub fn vertices(&self) -> &Vec<DVec3> {
+        return &self.vertices;
+    }
+
+    pub fn faces(&self) -> &Vec<Vec<usize>> {
+        return &self.faces;
+    }
+
+    pub fn lines(&self) -> &Vec<[usize; 2]> {
+        return &self.lines;
+    }
+
+    // the format is picked by extension
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+        let reader = BufReader::new(File::open(path)?);
+        return match extension.as_str() {
+            "obj" => read_obj(reader),
+            "off" => read_off(reader),
+            _ => Err(format!("unknown model format \"{extension}\", expected obj or off").into()),
+        };
+    }
+
+    // whether a file can be loaded as a model rather than as a point cloud
+    pub fn is_model(path: &Path) -> bool {
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+        return extension == "obj" || extension == "off";
+    }
+
+    // centered on location and scaled so the farthest vertex is scale away
+    pub fn into_shape(self, name: &str, scale: f64, location: DVec3) -> Shape3d {
+        let (min, max) = self.vertices.iter()
+            .fold((DVec3::MAX, DVec3::MIN), |(min, max), vertex| (min.min(*vertex), max.max(*vertex)));
+        let middle = (min + max) / 2.0;
+        let radius = self.vertices.iter().map(|vertex| vertex.distance(middle)).fold(0.0, f64::max);
+        let factor = if radius > 0.0 { scale / radius } else { 1.0 };
+        let vertices = self.vertices.iter().map(|vertex| (*vertex - middle) * factor + location).collect();
+        let mut shape = if self.faces.is_empty() {
+            Shape3d::new(vertices, self.lines, location)
+        } else {
+            Shape3d::with_faces(vertices, self.faces, location)
+        };
+        shape.set_name(name);
+        return shape;
+    }
+}
+
+// checks every index against the vertex count, flips y and turns the faces the other way round
+fn finish(mut model: Model) -> Result<Model, Box<dyn Error>> {
+    if model.vertices.is_empty() {
+        return Err("model has no vertices".into());
+    }
+    let vertex_count = model.vertices.len();
+    let out_of_range = model.faces.iter().flatten().chain(model.lines.iter().flatten()).find(|index| **index >= vertex_count);
+    if let Some(index) = out_of_range {
+        return Err(format!("model refers to vertex {index} but only has {vertex_count}").into());
+    }
+    for vertex in model.vertices.iter_mut() {
+        vertex.y = -vertex.y;
+    }
+    for face in model.faces.iter_mut() {
+        face.reverse();
+    }
+    return Ok(model);
+}
+
+// wavefront obj, only the v, f and l elements are read. f and l indices start at 1, negative ones count back from
+// the last vertex read so far. texture and normal indices after a slash are ignored
+fn read_obj(reader: impl BufRead) -> Result<Model, Box<dyn Error>> {
+    let mut model = Model { vertices: vec![], faces: vec![], lines: vec![] };
+    for line in reader.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let index = |word: &str| -> Result<usize, Box<dyn Error>> {
+            let index: i64 = word.split('/').next().unwrap_or("").parse()?;
+            let vertex_count = model.vertices.len() as i64;
+            return match index {
+                0 => Err("obj indices start at 1".into()),
+                index if index < 0 && vertex_count + index >= 0 => Ok((vertex_count + index) as usize),
+                index if index < 0 => Err(format!("obj index {index} is before the first vertex").into()),
+                index => Ok(index as usize - 1),
+            };
+        };
+        match words.first() {
+            Some(&"v") if words.len() >= 4 => {
+                let values = words[1..4].iter().map(|word| word.parse()).collect::<Result<Vec<f64>, _>>()?;
+                model.vertices.push(DVec3::new(values[0], values[1], values[2]));
+            }
+            Some(&"v") => return Err(format!("obj vertex needs x, y and z: {line}").into()),
+            Some(&"f") if words.len() >= 4 => {
+                let face = words[1..].iter().map(|word| index(word)).collect::<Result<Vec<_>, _>>()?;
+                model.faces.push(face);
+            }
+            Some(&"f") => return Err(format!("obj face needs at least 3 vertices: {line}").into()),
+            Some(&"l") if words.len() >= 3 => {
+                let polyline = words[1..].iter().map(|word| index(word)).collect::<Result<Vec<_>, _>>()?;
+                model.lines.extend(polyline.windows(2).map(|pair| [pair[0], pair[1]]));
+            }
+            Some(&"l") => return Err(format!("obj line needs at least 2 vertices: {line}").into()),
+            _ => {}
+        }
+    }
+    return finish(model);
+}
+
+// geomview's object file format: OFF, then the vertex, face and edge counts, the vertices and the faces as a
+// vertex count followed by that many indices. the counts may follow OFF on the same line, colors after a vertex
+// or face are ignored
+fn read_off(reader: impl BufRead) -> Result<Model, Box<dyn Error>> {
+    let mut lines = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.split('#').next().unwrap_or("").trim().to_string();
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    let mut lines = lines.into_iter();
+    let header = lines.next().ok_or("off file is empty")?;
+    let mut counts = match header.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["OFF"] => lines.next().ok_or("off file has no vertex and face counts")?,
+        ["OFF", counts @ ..] => counts.join(" "),
+        _ => return Err("off file does not start with OFF".into()),
+    }.split_whitespace().map(|word| word.parse::<usize>()).collect::<Result<Vec<_>, _>>()?.into_iter();
+    let (vertex_count, face_count) = match (counts.next(), counts.next()) {
+        (Some(vertex_count), Some(face_count)) => (vertex_count, face_count),
+        _ => return Err("off file needs a vertex and a face count".into()),
+    };
+
+    let mut model = Model { vertices: vec![], faces: vec![], lines: vec![] };
+    for _ in 0..vertex_count {
+        let line = lines.next().ok_or("off file ended before its last vertex")?;
+        let values = line.split_whitespace().take(3).map(|word| word.parse()).collect::<Result<Vec<f64>, _>>()?;
+        if values.len() < 3 {
+            return Err(format!("off vertex needs x, y and z: {line}").into());
+        }
+        model.vertices.push(DVec3::new(values[0], values[1], values[2]));
+    }
+    for _ in 0..face_count {
+        let line = lines.next().ok_or("off file ended before its last face")?;
+        let mut words = line.split_whitespace();
+        let count: usize = words.next().unwrap_or("").parse()?;
+        let face = words.take(count).map(|word| word.parse()).collect::<Result<Vec<usize>, _>>()?;
+        if count < 3 || face.len() < count {
+            return Err(format!("off face needs at least 3 vertices: {line}").into());
+        }
+        model.faces.push(face);
+    }
+    return finish(model);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn obj_reads_faces_with_slashes_and_negative_indices() {
+        let file = "# a square and a triangle\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvn 0 0 1\nf 1/1/1 2/2/1 3/3/1 4/4/1\nv 0 0 1\nf -1 1 2\n";
+        let model = read_obj(file.as_bytes()).unwrap();
+        assert_eq!(model.vertices().len(), 5);
+        assert_eq!(model.vertices()[2], DVec3::new(1.0, -1.0, 0.0));
+        assert_eq!(model.faces(), &vec![vec![3, 2, 1, 0], vec![1, 0, 4]]);
+        assert!(model.lines().is_empty());
+    }
+
+    #[test]
+    fn obj_reads_polylines() {
+        let model = read_obj("v 0 0 0\nv 1 0 0\nv 1 1 0\nl 1 2 3\n".as_bytes()).unwrap();
+        assert!(model.faces().is_empty());
+        assert_eq!(model.lines(), &vec![[0, 1], [1, 2]]);
+    }
+
+    #[test]
+    fn obj_rejects_bad_indices() {
+        for file in ["v 0 0 0\nv 1 0 0\nf 1 2 3\n", "v 0 0 0\nf 0 1 1\n", "v 0 0 0\nf -2 1 1\n", "v 0 0\n", "v 0 0 0\nf 1 1\n", ""] {
+            assert!(read_obj(file.as_bytes()).is_err(), "{file}");
+        }
+    }
+
+    #[test]
+    fn off_reads_vertices_and_faces() {
+        let file = "OFF\n# a tetrahedron\n4 4 6\n0 0 0\n1 0 0\n0 1 0\n0 0 1 255 0 0\n3 0 2 1\n3 0 1 3\n3 0 3 2\n3 1 2 3 0.5 0.5 0.5\n";
+        let model = read_off(file.as_bytes()).unwrap();
+        assert_eq!(model.vertices()[2], DVec3::new(0.0, -1.0, 0.0));
+        assert_eq!(model.faces().len(), 4);
+        assert_eq!(model.faces()[0], vec![1, 2, 0]);
+        let inline = read_off("OFF 3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1 2\n".as_bytes()).unwrap();
+        assert_eq!(inline.faces(), &vec![vec![2, 1, 0]]);
+    }
+
+    #[test]
+    fn off_rejects_malformed_files() {
+        for file in ["", "COFF\n1 0 0\n0 0 0\n", "OFF\n2 1 0\n0 0 0\n", "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n3 0 1 5\n", "OFF\n3 1 0\n0 0 0\n1 0 0\n0 1 0\n4 0 1 2\n"] {
+            assert!(read_off(file.as_bytes()).is_err(), "{file}");
+        }
+    }
+}
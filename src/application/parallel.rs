@@ -0,0 +1,55 @@
+use glam::DVec3;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::application::{shape3d::Shape3d, projection::Projection, viewport::Viewport};
+
+// vertex arrays shorter than this stay on the calling thread, splitting them up costs more than it saves
+#[cfg(feature = "parallel")]
+const MIN_PARALLEL_VERTICES: usize = 16384;
+
+// without the parallel feature every function here runs serially on the calling thread
+
+// replaces every vertex with its transformed self
+pub fn transform<F: Fn(DVec3) -> DVec3 + Sync>(vertices: &mut [DVec3], transform: F) {
+    #[cfg(feature = "parallel")]
+    if vertices.len() >= MIN_PARALLEL_VERTICES {
+        vertices.par_iter_mut().for_each(|vertex| *vertex = transform(*vertex));
+        return;
+    }
+    for vertex in vertices.iter_mut() {
+        *vertex = transform(*vertex);
+    }
+}
+
+// screen position and depth of every vertex, None for the ones behind the eye
+pub fn project(vertices: &[DVec3], projection: &Projection, viewport: &Viewport) -> Vec<Option<DVec3>> {
+    #[cfg(feature = "parallel")]
+    if vertices.len() >= MIN_PARALLEL_VERTICES {
+        return vertices.par_iter().map(|vertex| projection.project(vertex, viewport)).collect();
+    }
+    return vertices.iter().map(|vertex| projection.project(vertex, viewport)).collect();
+}
+
+// like project, but only the vertices marked as used are projected, the others are left as None
+pub fn project_used(vertices: &[DVec3], used: &[bool], projection: &Projection, viewport: &Viewport) -> Vec<Option<DVec3>> {
+    let project = |(vertex, used): (&DVec3, &bool)| if *used { projection.project(vertex, viewport) } else { None };
+    #[cfg(feature = "parallel")]
+    if vertices.len() >= MIN_PARALLEL_VERTICES {
+        return vertices.par_iter().zip(used.par_iter()).map(project).collect();
+    }
+    return vertices.iter().zip(used.iter()).map(project).collect();
+}
+
+// brings the world vertices of every shape up to date, each shape on its own thread when the feature is on.
+// projecting them depends on the view and is left to the views, large arrays are still split up there
+pub fn prepare(shapes: &[Shape3d]) {
+    #[cfg(feature = "parallel")]
+    shapes.par_iter().for_each(|shape| {
+        shape.vertices();
+    });
+    #[cfg(not(feature = "parallel"))]
+    for shape in shapes.iter() {
+        shape.vertices();
+    }
+}
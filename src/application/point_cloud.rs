@@ -0,0 +1,521 @@
+use std::{error::Error, fs::File, io::{BufRead, BufReader, Read}, path::Path};
+
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, style::Style};
+
+// a list of points with optional per point color and intensity, as read from a scan
+#[derive(Debug)]
+pub struct PointCloud {
+    points: Vec<DVec3>,
+    colors: Option<Vec<Color>>,
+    intensities: Option<Vec<f64>>,
+}
+
+impl PointCloud {
+    pub fn points(&self) -> &Vec<DVec3> {
+        return &self.points;
+    }
+
+    pub fn colors(&self) -> Option<&Vec<Color>> {
+        return self.colors.as_ref();
+    }
+
+    pub fn intensities(&self) -> Option<&Vec<f64>> {
+        return self.intensities.as_ref();
+    }
+
+    // the format is picked by extension, decimation keeps every nth point so large files stay quick to view
+    pub fn load(path: &Path, decimation: usize) -> Result<Self, Box<dyn Error>> {
+        let decimation = decimation.max(1);
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+        let reader = BufReader::new(File::open(path)?);
+        return match extension.as_str() {
+            "xyz" | "txt" | "csv" | "pts" => read_xyz(reader, decimation),
+            "pcd" => read_pcd(reader, decimation),
+            "las" => read_las(reader, decimation),
+            "laz" => Err("compressed LAZ files are not supported, decompress them to LAS first".into()),
+            _ => Err(format!("unknown point cloud format \"{extension}\", expected xyz, txt, csv, pts, pcd or las").into()),
+        };
+    }
+
+    // centered on location and scaled so the farthest point is scale away, with colors or else intensities as grays
+    pub fn into_shape(self, name: &str, scale: f64, location: DVec3) -> Shape3d {
+        let (min, max) = self.points.iter()
+            .fold((DVec3::MAX, DVec3::MIN), |(min, max), point| (min.min(*point), max.max(*point)));
+        let middle = (min + max) / 2.0;
+        let radius = self.points.iter().map(|point| point.distance(middle)).fold(0.0, f64::max);
+        let factor = if radius > 0.0 { scale / radius } else { 1.0 };
+        let vertices = self.points.iter().map(|point| (*point - middle) * factor + location).collect();
+
+        let vertex_colors = match (self.colors, self.intensities) {
+            (Some(colors), _) => Some(colors),
+            (None, Some(intensities)) => {
+                let (low, high) = intensities.iter().fold((f64::MAX, f64::MIN), |(low, high), value| (low.min(*value), high.max(*value)));
+                Some(intensities.iter().map(|value| {
+                    let gray = if high > low { (value - low) / (high - low) } else { 1.0 };
+                    let gray = (gray * 255.0).round() as u8;
+                    Color::RGB(gray, gray, gray)
+                }).collect())
+            }
+            (None, None) => None,
+        };
+        let mut shape = Shape3d::new(vertices, vec![], location);
+        shape.set_name(name);
+        shape.set_style(Style { vertex_colors, ..Style::default() });
+        return shape;
+    }
+}
+
+// one point per line as x y z, x y z intensity, x y z r g b or x y z intensity r g b,
+// separated by spaces, tabs or commas. lines that are not all numbers, like csv headers and comments, are skipped
+fn read_xyz(reader: impl BufRead, decimation: usize) -> Result<PointCloud, Box<dyn Error>> {
+    let mut cloud = PointCloud { points: vec![], colors: Some(vec![]), intensities: Some(vec![]) };
+    let mut index = 0;
+    for line in reader.lines() {
+        let line = line?;
+        let values: Result<Vec<f64>, _> = line.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.parse::<f64>())
+            .collect();
+        let values = match values {
+            Ok(values) if values.len() >= 3 => values,
+            _ => continue,
+        };
+        index += 1;
+        if (index - 1) % decimation != 0 {
+            continue;
+        }
+        cloud.points.push(DVec3::new(values[0], values[1], values[2]));
+        let (intensity, rgb) = match values.len() {
+            4 => (Some(values[3]), None),
+            6 => (None, Some(&values[3..6])),
+            7 => (Some(values[3]), Some(&values[4..7])),
+            _ => (None, None),
+        };
+        match (&mut cloud.colors, rgb) {
+            (Some(colors), Some(rgb)) => colors.push(color_from_channels(rgb)),
+            _ => cloud.colors = None,
+        }
+        match (&mut cloud.intensities, intensity) {
+            (Some(intensities), Some(intensity)) => intensities.push(intensity),
+            _ => cloud.intensities = None,
+        }
+    }
+    if cloud.points.is_empty() {
+        cloud.colors = None;
+        cloud.intensities = None;
+    }
+    return Ok(cloud);
+}
+
+// channels are 0 to 255, unless every one of them is a fraction no bigger than 1
+fn color_from_channels(rgb: &[f64]) -> Color {
+    let unit = rgb.iter().all(|channel| *channel <= 1.0) && rgb.iter().any(|channel| channel.fract() != 0.0);
+    let channel = |value: f64| (if unit { value * 255.0 } else { value }).round().clamp(0.0, 255.0) as u8;
+    return Color::RGB(channel(rgb[0]), channel(rgb[1]), channel(rgb[2]));
+}
+
+// more values than any pcl descriptor has, so a broken header cannot ask for huge point records
+const MAX_PCD_COUNT: usize = 4096;
+
+#[derive(Debug)]
+struct PcdField {
+    name: String,
+    size: usize, // bytes per value
+    kind: char, // I signed, U unsigned or F floating point
+    count: usize,
+}
+
+// pcl's point cloud data format, a text header followed by ascii or binary points
+fn read_pcd(mut reader: impl BufRead, decimation: usize) -> Result<PointCloud, Box<dyn Error>> {
+    let mut names: Vec<String> = vec![];
+    let mut sizes: Vec<usize> = vec![];
+    let mut kinds: Vec<char> = vec![];
+    let mut counts: Vec<usize> = vec![];
+    let mut point_count = None;
+    let mut width_height: (usize, usize) = (0, 1);
+    let data = loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err("pcd header ended before its DATA line".into());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.first() {
+            None => {}
+            Some(word) if word.starts_with('#') => {}
+            Some(&"FIELDS") => names = words[1..].iter().map(|word| word.to_string()).collect(),
+            Some(&"SIZE") => sizes = words[1..].iter().map(|word| word.parse()).collect::<Result<_, _>>()?,
+            Some(&"TYPE") => kinds = words[1..].iter().map(|word| word.chars().next().unwrap_or('F')).collect(),
+            Some(&"COUNT") => counts = words[1..].iter().map(|word| word.parse()).collect::<Result<_, _>>()?,
+            Some(&"WIDTH") if words.len() == 2 => width_height.0 = words[1].parse()?,
+            Some(&"HEIGHT") if words.len() == 2 => width_height.1 = words[1].parse()?,
+            Some(&"POINTS") if words.len() == 2 => point_count = Some(words[1].parse::<usize>()?),
+            Some(&"DATA") if words.len() == 2 => break words[1].to_string(),
+            Some(_) => {}
+        }
+    };
+    if counts.is_empty() {
+        counts = vec![1; names.len()];
+    }
+    if sizes.len() != names.len() || kinds.len() != names.len() || counts.len() != names.len() {
+        return Err("pcd FIELDS, SIZE, TYPE and COUNT have different lengths".into());
+    }
+    let fields: Vec<PcdField> = names.into_iter().zip(sizes).zip(kinds).zip(counts)
+        .map(|(((name, size), kind), count)| PcdField { name, size, kind, count })
+        .collect();
+    for field in fields.iter() {
+        if ![1, 2, 4, 8].contains(&field.size) {
+            return Err(format!("pcd field {} has SIZE {}, only 1, 2, 4 and 8 are supported", field.name, field.size).into());
+        }
+        if field.count == 0 || field.count > MAX_PCD_COUNT {
+            return Err(format!("pcd field {} has COUNT {}, it has to be between 1 and {MAX_PCD_COUNT}", field.name, field.count).into());
+        }
+    }
+    let point_count = match point_count {
+        Some(point_count) => point_count,
+        None => width_height.0.checked_mul(width_height.1).ok_or("pcd WIDTH times HEIGHT is too large")?,
+    };
+
+    // index of the first value of each field within a point
+    let mut starts = vec![];
+    let mut values_per_point = 0;
+    for field in fields.iter() {
+        starts.push(values_per_point);
+        values_per_point += field.count;
+    }
+    let find = |name: &str| fields.iter().position(|field| field.name == name);
+    let (x, y, z) = match (find("x"), find("y"), find("z")) {
+        (Some(x), Some(y), Some(z)) => (x, y, z),
+        _ => return Err("pcd file has no x, y and z fields".into()),
+    };
+    let rgb = find("rgb").or(find("rgba"));
+    let intensity = find("intensity");
+
+    let mut cloud = PointCloud {
+        points: vec![],
+        colors: rgb.map(|_| vec![]),
+        intensities: intensity.map(|_| vec![]),
+    };
+    // values are kept as f64 next to their raw bits, packed colors need the bits
+    let mut add_point = |values: &[(f64, u64)]| {
+        let point = DVec3::new(values[starts[x]].0, values[starts[y]].0, values[starts[z]].0);
+        // organized clouds mark missing points with NaN
+        if !point.is_finite() {
+            return;
+        }
+        cloud.points.push(point);
+        if let (Some(colors), Some(rgb)) = (&mut cloud.colors, rgb) {
+            let packed = values[starts[rgb]].1 as u32;
+            colors.push(Color::RGB((packed >> 16) as u8, (packed >> 8) as u8, packed as u8));
+        }
+        if let (Some(intensities), Some(intensity)) = (&mut cloud.intensities, intensity) {
+            intensities.push(values[starts[intensity]].0);
+        }
+    };
+
+    let kinds: Vec<(char, usize)> = fields.iter().flat_map(|field| std::iter::repeat_n((field.kind, field.size), field.count)).collect();
+    match data.as_str() {
+        "ascii" => {
+            let mut index = 0;
+            for line in reader.lines() {
+                let line = line?;
+                let words: Vec<&str> = line.split_whitespace().collect();
+                if words.is_empty() {
+                    continue;
+                }
+                if words.len() != values_per_point {
+                    return Err(format!("pcd point {} has {} values instead of {values_per_point}", index + 1, words.len()).into());
+                }
+                index += 1;
+                if (index - 1) % decimation != 0 {
+                    continue;
+                }
+                let values = words.iter().zip(kinds.iter())
+                    .map(|(word, (kind, size))| parse_pcd_ascii(word, *kind, *size))
+                    .collect::<Result<Vec<_>, _>>()?;
+                add_point(&values);
+            }
+        }
+        "binary" => {
+            let point_size: usize = kinds.iter().map(|(_, size)| size).sum();
+            let mut record = vec![0; point_size];
+            let mut skipped = vec![0; point_size];
+            for index in 0..point_count {
+                if index % decimation != 0 {
+                    reader.read_exact(&mut skipped)?;
+                    continue;
+                }
+                reader.read_exact(&mut record)?;
+                let mut offset = 0;
+                let mut values = Vec::with_capacity(kinds.len());
+                for (kind, size) in kinds.iter() {
+                    values.push(read_binary_value(&record[offset..offset + size], *kind)?);
+                    offset += size;
+                }
+                add_point(&values);
+            }
+        }
+        "binary_compressed" => return Err("compressed pcd files are not supported, save them as ascii or binary".into()),
+        _ => return Err(format!("unknown pcd DATA type {data}").into()),
+    }
+    return Ok(cloud);
+}
+
+// packed colors are written as the float with the same bits, or as a plain integer
+fn parse_pcd_ascii(word: &str, kind: char, size: usize) -> Result<(f64, u64), Box<dyn Error>> {
+    return match kind {
+        'F' if size == 4 => {
+            let value: f32 = word.parse()?;
+            Ok((value as f64, value.to_bits() as u64))
+        }
+        'F' => {
+            let value: f64 = word.parse()?;
+            Ok((value, value.to_bits()))
+        }
+        'I' => {
+            let value: i64 = word.parse()?;
+            Ok((value as f64, value as u64))
+        }
+        _ => {
+            let value: u64 = word.parse()?;
+            Ok((value as f64, value))
+        }
+    };
+}
+
+// little endian, as pcl writes it
+fn read_binary_value(bytes: &[u8], kind: char) -> Result<(f64, u64), Box<dyn Error>> {
+    let mut buffer = [0; 8];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    let bits = u64::from_le_bytes(buffer);
+    let value = match (kind, bytes.len()) {
+        ('F', 4) => f32::from_bits(bits as u32) as f64,
+        ('F', 8) => f64::from_bits(bits),
+        ('I', 1) => bits as u8 as i8 as f64,
+        ('I', 2) => bits as u16 as i16 as f64,
+        ('I', 4) => bits as u32 as i32 as f64,
+        ('I', 8) => bits as i64 as f64,
+        ('U', 1 | 2 | 4 | 8) => bits as f64,
+        _ => return Err(format!("unsupported pcd value of type {kind} and size {}", bytes.len()).into()),
+    };
+    return Ok((value, bits));
+}
+
+// asprs lidar format 1.2 and later, point formats 0 to 10
+fn read_las(mut reader: impl Read, decimation: usize) -> Result<PointCloud, Box<dyn Error>> {
+    let mut header = vec![0; 227];
+    reader.read_exact(&mut header)?;
+    if &header[0..4] != b"LASF" {
+        return Err("not a las file".into());
+    }
+    let (major, minor) = (header[24], header[25]);
+    if (major, minor) < (1, 2) {
+        return Err(format!("las {major}.{minor} is not supported, 1.2 or later is needed").into());
+    }
+    let u16_at = |offset: usize| u16::from_le_bytes([header[offset], header[offset + 1]]);
+    let u32_at = |offset: usize| u32::from_le_bytes(header[offset..offset + 4].try_into().unwrap());
+    let f64_at = |offset: usize| f64::from_le_bytes(header[offset..offset + 8].try_into().unwrap());
+
+    let header_size = u16_at(94) as usize;
+    let point_offset = u32_at(96) as usize;
+    let format = header[104];
+    let record_length = u16_at(105) as usize;
+    let scale = DVec3::new(f64_at(131), f64_at(139), f64_at(147));
+    let offset = DVec3::new(f64_at(155), f64_at(163), f64_at(171));
+    if format & 0x80 != 0 {
+        return Err("compressed LAZ point data is not supported, decompress it to LAS first".into());
+    }
+    let mut point_count = u32_at(107) as u64;
+    // las 1.4 keeps counts past 32 bits in the extended header
+    if point_count == 0 && (major, minor) >= (1, 4) && header_size >= 255 {
+        let mut extended = vec![0; 255 - header.len()];
+        reader.read_exact(&mut extended)?;
+        header.extend(extended);
+        point_count = u64::from_le_bytes(header[247..255].try_into().unwrap());
+    }
+    // byte offset of the red, green and blue values within a point record
+    let rgb_offset = match format {
+        0 | 1 | 4 | 6 | 9 => None,
+        2 => Some(20),
+        3 | 5 => Some(28),
+        7 | 8 | 10 => Some(30),
+        _ => return Err(format!("unknown las point format {format}").into()),
+    };
+    if record_length < rgb_offset.map_or(20, |offset| offset + 6) {
+        return Err(format!("las point records of {record_length} bytes are too short for point format {format}").into());
+    }
+
+    // skip the rest of the header and the variable length records
+    if point_offset < header.len() {
+        return Err("las point data starts inside the header".into());
+    }
+    std::io::copy(&mut (&mut reader).take((point_offset - header.len()) as u64), &mut std::io::sink())?;
+
+    let mut cloud = PointCloud { points: vec![], colors: rgb_offset.map(|_| vec![]), intensities: Some(vec![]) };
+    let mut raw_colors: Vec<[u16; 3]> = vec![];
+    let mut record = vec![0; record_length];
+    for index in 0..point_count {
+        reader.read_exact(&mut record)?;
+        if index % decimation as u64 != 0 {
+            continue;
+        }
+        let i32_at = |offset: usize| i32::from_le_bytes(record[offset..offset + 4].try_into().unwrap()) as f64;
+        let u16_at = |offset: usize| u16::from_le_bytes([record[offset], record[offset + 1]]);
+        cloud.points.push(DVec3::new(i32_at(0), i32_at(4), i32_at(8)) * scale + offset);
+        if let Some(intensities) = &mut cloud.intensities {
+            intensities.push(u16_at(12) as f64);
+        }
+        if let Some(rgb_offset) = rgb_offset {
+            raw_colors.push([u16_at(rgb_offset), u16_at(rgb_offset + 2), u16_at(rgb_offset + 4)]);
+        }
+    }
+    // the spec asks for 16 bit colors but plenty of files store 8 bit ones
+    let shift = if raw_colors.iter().flatten().any(|channel| *channel > 255) { 8 } else { 0 };
+    if let Some(colors) = &mut cloud.colors {
+        colors.extend(raw_colors.iter().map(|[r, g, b]| Color::RGB((r >> shift) as u8, (g >> shift) as u8, (b >> shift) as u8)));
+    }
+    return Ok(cloud);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pcd_header(fields: &str, size: &str, kind: &str, count: &str, points: usize, data: &str) -> Vec<u8> {
+        return format!("# .PCD v0.7\nVERSION 0.7\nFIELDS {fields}\nSIZE {size}\nTYPE {kind}\nCOUNT {count}\nWIDTH {points}\nHEIGHT 1\nPOINTS {points}\nDATA {data}\n").into_bytes();
+    }
+
+    // a las 1.2 file with point format 2, so every point has an intensity and a color
+    fn las(points: &[([i32; 3], u16, [u16; 3])]) -> Vec<u8> {
+        let mut bytes = vec![0; 227];
+        bytes[0..4].copy_from_slice(b"LASF");
+        bytes[24] = 1;
+        bytes[25] = 2;
+        bytes[94..96].copy_from_slice(&227u16.to_le_bytes());
+        bytes[96..100].copy_from_slice(&227u32.to_le_bytes());
+        bytes[104] = 2;
+        bytes[105..107].copy_from_slice(&26u16.to_le_bytes());
+        bytes[107..111].copy_from_slice(&(points.len() as u32).to_le_bytes());
+        for axis in 0..3 {
+            bytes[131 + axis * 8..139 + axis * 8].copy_from_slice(&0.5f64.to_le_bytes());
+            bytes[155 + axis * 8..163 + axis * 8].copy_from_slice(&10.0f64.to_le_bytes());
+        }
+        for (position, intensity, rgb) in points.iter() {
+            let mut record = vec![0; 26];
+            for axis in 0..3 {
+                record[axis * 4..axis * 4 + 4].copy_from_slice(&position[axis].to_le_bytes());
+            }
+            record[12..14].copy_from_slice(&intensity.to_le_bytes());
+            for channel in 0..3 {
+                record[20 + channel * 2..22 + channel * 2].copy_from_slice(&rgb[channel].to_le_bytes());
+            }
+            bytes.extend(record);
+        }
+        return bytes;
+    }
+
+    #[test]
+    fn xyz_skips_headers_and_reads_colors() {
+        let cloud = read_xyz("x,y,z,r,g,b\n1,2,3,255,0,0\n4 5 6 0 255 0\n".as_bytes(), 1).unwrap();
+        assert_eq!(cloud.points(), &vec![DVec3::new(1.0, 2.0, 3.0), DVec3::new(4.0, 5.0, 6.0)]);
+        assert_eq!(cloud.colors(), Some(&vec![Color::RGB(255, 0, 0), Color::RGB(0, 255, 0)]));
+        assert!(cloud.intensities().is_none());
+    }
+
+    #[test]
+    fn xyz_drops_attributes_not_every_line_has() {
+        let cloud = read_xyz("1 2 3 0.5\n4 5 6\n".as_bytes(), 1).unwrap();
+        assert_eq!(cloud.points().len(), 2);
+        assert!(cloud.intensities().is_none());
+        assert!(cloud.colors().is_none());
+    }
+
+    #[test]
+    fn xyz_decimates_and_ignores_short_lines() {
+        let cloud = read_xyz("1 2\n0 0 0\n1 1 1\n2 2 2\n3 3 3\n".as_bytes(), 2).unwrap();
+        assert_eq!(cloud.points(), &vec![DVec3::ZERO, DVec3::splat(2.0)]);
+        assert!(read_xyz("".as_bytes(), 1).unwrap().points().is_empty());
+    }
+
+    #[test]
+    fn pcd_ascii_reads_points_and_packed_colors() {
+        let mut file = pcd_header("x y z rgb", "4 4 4 4", "F F F U", "1 1 1 1", 2, "ascii");
+        file.extend(format!("1 2 3 {}\nnan nan nan 0\n", 0x00ff8000u32).into_bytes());
+        let cloud = read_pcd(file.as_slice(), 1).unwrap();
+        assert_eq!(cloud.points(), &vec![DVec3::new(1.0, 2.0, 3.0)]);
+        assert_eq!(cloud.colors(), Some(&vec![Color::RGB(255, 128, 0)]));
+    }
+
+    #[test]
+    fn pcd_binary_reads_points_and_intensities() {
+        let mut file = pcd_header("x y z intensity", "4 4 4 2", "F F F U", "1 1 1 1", 2, "binary");
+        for (point, intensity) in [([1.0f32, 2.0, 3.0], 7u16), ([4.0, 5.0, 6.0], 9)] {
+            point.iter().for_each(|value| file.extend(value.to_le_bytes()));
+            file.extend(intensity.to_le_bytes());
+        }
+        let cloud = read_pcd(file.as_slice(), 1).unwrap();
+        assert_eq!(cloud.points(), &vec![DVec3::new(1.0, 2.0, 3.0), DVec3::new(4.0, 5.0, 6.0)]);
+        assert_eq!(cloud.intensities(), Some(&vec![7.0, 9.0]));
+    }
+
+    #[test]
+    fn pcd_rejects_malformed_headers() {
+        let truncated = b"VERSION 0.7\nFIELDS x y z\nSIZE 4 4 4\n".to_vec();
+        let mismatched = pcd_header("x y z", "4 4", "F F F", "1 1 1", 1, "ascii");
+        let no_z = pcd_header("x y", "4 4", "F F", "1 1", 1, "ascii");
+        let wide = pcd_header("x y z", "4 4 16", "F F F", "1 1 1", 1, "binary");
+        let empty = pcd_header("x y z", "4 4 4", "F F F", "1 1 0", 1, "binary");
+        let huge = pcd_header("x y z", "4 4 4", "F F F", "1 1 1000000000", 1, "binary");
+        let compressed = pcd_header("x y z", "4 4 4", "F F F", "1 1 1", 1, "binary_compressed");
+        let overflowing = format!("FIELDS x y z\nSIZE 4 4 4\nTYPE F F F\nWIDTH {}\nHEIGHT 2\nDATA ascii\n", usize::MAX).into_bytes();
+        for file in [truncated, mismatched, no_z, wide, empty, huge, compressed, overflowing] {
+            assert!(read_pcd(file.as_slice(), 1).is_err(), "{}", String::from_utf8_lossy(&file));
+        }
+    }
+
+    #[test]
+    fn pcd_rejects_truncated_points() {
+        let mut ascii = pcd_header("x y z", "4 4 4", "F F F", "1 1 1", 1, "ascii");
+        ascii.extend(b"1 2\n");
+        assert!(read_pcd(ascii.as_slice(), 1).is_err());
+        let mut binary = pcd_header("x y z", "4 4 4", "F F F", "1 1 1", 2, "binary");
+        binary.extend([0; 12 + 5]);
+        assert!(read_pcd(binary.as_slice(), 1).is_err());
+    }
+
+    #[test]
+    fn las_reads_scaled_points_with_colors() {
+        let file = las(&[([2, 4, 6], 100, [255, 0, 0]), ([0, 0, 0], 200, [0, 0, 255])]);
+        let cloud = read_las(file.as_slice(), 1).unwrap();
+        assert_eq!(cloud.points(), &vec![DVec3::new(11.0, 12.0, 13.0), DVec3::splat(10.0)]);
+        assert_eq!(cloud.intensities(), Some(&vec![100.0, 200.0]));
+        assert_eq!(cloud.colors(), Some(&vec![Color::RGB(255, 0, 0), Color::RGB(0, 0, 255)]));
+        assert_eq!(read_las(file.as_slice(), 2).unwrap().points().len(), 1);
+    }
+
+    #[test]
+    fn las_rejects_malformed_headers() {
+        let file = las(&[([0, 0, 0], 0, [0, 0, 0])]);
+        let mut signature = file.clone();
+        signature[0] = b'X';
+        let mut version = file.clone();
+        version[25] = 1;
+        let mut short_records = file.clone();
+        short_records[105..107].copy_from_slice(&10u16.to_le_bytes());
+        let mut format = file.clone();
+        format[104] = 42;
+        let mut inside_header = file.clone();
+        inside_header[96..100].copy_from_slice(&100u32.to_le_bytes());
+        for file in [file[..200].to_vec(), signature, version, short_records, format, inside_header] {
+            assert!(read_las(file.as_slice(), 1).is_err());
+        }
+    }
+
+    #[test]
+    fn las_rejects_truncated_points() {
+        let file = las(&[([0, 0, 0], 0, [0, 0, 0]), ([1, 1, 1], 0, [0, 0, 0])]);
+        assert!(read_las(&file[..file.len() - 3], 1).is_err());
+    }
+}
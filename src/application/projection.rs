@@ -0,0 +1,129 @@
+use glam::{DVec3, DQuat};
+use sdl2::rect::Point;
+
+use crate::application::viewport::Viewport;
+
+// points closer to the perspective eye than this are clipped away
+pub const NEAR: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lens {
+    Orthographic { scale: f64 }, // drops z, the viewer looks down +z from infinitely far away. scale is pixels per world unit
+    Perspective { distance: f64 }, // pinhole eye at (0, 0, -distance) looking down +z, the z = 0 plane keeps its orthographic size
+}
+
+impl Lens {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Lens::Orthographic { .. } => "Orthographic",
+            Lens::Perspective { .. } => "Perspective",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            Lens::Orthographic { .. } => Lens::Perspective { distance: 800.0 },
+            Lens::Perspective { .. } => Lens::Orthographic { scale: 1.0 },
+        };
+    }
+
+    // the same lens zoomed to scale pixels per world unit, perspective keeps its own size
+    pub fn with_scale(&self, scale: f64) -> Self {
+        return match self {
+            Lens::Orthographic { .. } => Lens::Orthographic { scale },
+            Lens::Perspective { .. } => *self,
+        };
+    }
+}
+
+// a lens looking at the world from a view. the world is moved so the view's target is at the origin and then turned
+// by the view's orientation, the shapes themselves stay where they are. every point given to it is in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    pub lens: Lens,
+    orientation: DQuat,
+    target: DVec3,
+}
+
+impl Projection {
+    // looks at the world origin without turning it
+    pub fn new(lens: Lens) -> Self {
+        return Self { lens, orientation: DQuat::IDENTITY, target: DVec3::ZERO };
+    }
+
+    pub fn with_view(&self, orientation: DQuat, target: DVec3) -> Self {
+        return Self { orientation, target, ..*self };
+    }
+
+    // where a world point is as the lens sees it, with the depth along +z
+    pub fn to_view(&self, point: &DVec3) -> DVec3 {
+        return self.orientation.mul_vec3(*point - self.target);
+    }
+
+    // the world direction a view space direction points in
+    pub fn from_view_direction(&self, direction: &DVec3) -> DVec3 {
+        return self.orientation.inverse().mul_vec3(*direction);
+    }
+
+    // maps a world point to (screen x, screen y, depth), None if it is behind the eye
+    pub fn project(&self, point: &DVec3, viewport: &Viewport) -> Option<DVec3> {
+        let point = self.to_view(point);
+        let center = viewport.center();
+        let pixel_scale = viewport.pixel_scale();
+        return match self.lens {
+            Lens::Orthographic { scale } => Some(DVec3::new(point.x * scale * pixel_scale + center.0, point.y * scale * pixel_scale + center.1, point.z)),
+            Lens::Perspective { distance } => {
+                let depth = point.z + distance;
+                if depth < NEAR {
+                    return None;
+                }
+                let scale = distance / depth * pixel_scale;
+                Some(DVec3::new(point.x * scale + center.0, point.y * scale + center.1, point.z))
+            }
+        };
+    }
+
+    // unit world direction the viewer looks along to see the point
+    pub fn view_direction(&self, point: &DVec3) -> DVec3 {
+        return match self.lens {
+            Lens::Orthographic { .. } => self.from_view_direction(&DVec3::Z),
+            Lens::Perspective { distance } => self.from_view_direction(&(self.to_view(point) - DVec3::new(0.0, 0.0, -distance)).normalize_or_zero()),
+        };
+    }
+
+    // how far the point is from the eye along the view direction, infinite for orthographic
+    pub fn eye_distance(&self, point: &DVec3) -> f64 {
+        return match self.lens {
+            Lens::Orthographic { .. } => f64::INFINITY,
+            Lens::Perspective { distance } => self.to_view(point).distance(DVec3::new(0.0, 0.0, -distance)),
+        };
+    }
+
+    // trims the part of a segment that lies behind the near plane, None if nothing is left
+    pub fn clip_segment(&self, a: &DVec3, b: &DVec3) -> Option<(DVec3, DVec3)> {
+        let near_z = match self.lens {
+            Lens::Orthographic { .. } => return Some((*a, *b)),
+            Lens::Perspective { distance } => NEAR - distance,
+        };
+        let (a_z, b_z) = (self.to_view(a).z, self.to_view(b).z);
+        if a_z < near_z && b_z < near_z {
+            return None;
+        }
+        if a_z >= near_z && b_z >= near_z {
+            return Some((*a, *b));
+        }
+        let crossing = a.lerp(*b, (near_z - a_z) / (b_z - a_z));
+        return if a_z < near_z { Some((crossing, *b)) } else { Some((*a, crossing)) };
+    }
+
+    // clips and projects a segment, returning the screen space end points
+    pub fn project_segment(&self, a: &DVec3, b: &DVec3, viewport: &Viewport) -> Option<(DVec3, DVec3)> {
+        let (a, b) = self.clip_segment(a, b)?;
+        return Some((self.project(&a, viewport)?, self.project(&b, viewport)?));
+    }
+}
+
+pub fn to_point(screen: &DVec3) -> Point {
+    return Point::new(screen.x as i32, screen.y as i32);
+}
+
@@ -0,0 +1,113 @@
+use std::{error::Error, fs::{self, File}, path::PathBuf};
+
+use sdl2::{render::Canvas, video::Window, pixels::PixelFormatEnum, surface::Surface, image::SaveSurface};
+
+#[derive(Debug, Clone)]
+pub enum RecordFormat {
+    PngSequence(PathBuf), // directory that receives frame_00000.png, frame_00001.png, ...
+    Gif(PathBuf),
+}
+
+pub struct Recorder {
+    format: RecordFormat,
+    frame_rate: f64,
+    duration: Option<f64>, // seconds of output, None records until stopped
+    time_since_frame: f64,
+    frame_count: usize,
+    frame_size: Option<(u32, u32)>,
+    gif: Option<gif::Encoder<File>>,
+}
+
+impl Recorder {
+    pub fn new(format: RecordFormat, frame_rate: f64, duration: Option<f64>) -> Result<Self, Box<dyn Error>> {
+        if frame_rate <= 0.0 {
+            return Err(format!("frame rate must be positive, got {frame_rate}").into());
+        }
+        if let RecordFormat::PngSequence(directory) = &format {
+            fs::create_dir_all(directory)?;
+        }
+        let frame_interval = 1.0 / frame_rate;
+        return Ok(Self {
+            format,
+            frame_rate,
+            duration,
+            time_since_frame: frame_interval, // capture the very first frame
+            frame_count: 0,
+            frame_size: None,
+            gif: None,
+        });
+    }
+
+    pub fn format(&self) -> &RecordFormat {
+        return &self.format;
+    }
+
+    pub fn frame_count(&self) -> usize {
+        return self.frame_count;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        return match self.duration {
+            Some(duration) => self.frame_count as f64 >= (duration * self.frame_rate).round(),
+            None => false,
+        };
+    }
+
+    // returns true when enough time has passed that the next presented frame should be captured
+    pub fn tick(&mut self, delta_time: f64) -> bool {
+        if self.is_finished() {
+            return false;
+        }
+        self.time_since_frame += delta_time;
+        let frame_interval = 1.0 / self.frame_rate;
+        if self.time_since_frame >= frame_interval {
+            self.time_since_frame = (self.time_since_frame - frame_interval) % frame_interval;
+            return true;
+        }
+        return false;
+    }
+
+    // reads back what has been drawn so far, so call this before the canvas is presented
+    pub fn capture(&mut self, canvas: &Canvas<Window>) -> Result<(), Box<dyn Error>> {
+        let (width, height) = canvas.output_size()?;
+        let mut pixels = canvas.read_pixels(None, PixelFormatEnum::RGB24)?;
+        return self.push_frame(width, height, &mut pixels);
+    }
+
+    // accepts tightly packed RGB24 pixels from any renderer
+    pub fn push_frame(&mut self, width: u32, height: u32, pixels: &mut [u8]) -> Result<(), Box<dyn Error>> {
+        match self.frame_size {
+            None => self.frame_size = Some((width, height)),
+            Some(size) if size != (width, height) => {
+                return Err(format!("frame size changed from {size:?} to {:?} while recording", (width, height)).into());
+            }
+            Some(_) => {}
+        }
+        match &self.format {
+            RecordFormat::PngSequence(directory) => {
+                let surface = Surface::from_data(pixels, width, height, width * 3, PixelFormatEnum::RGB24)?;
+                surface.save(directory.join(format!("frame_{:05}.png", self.frame_count)))?;
+            }
+            RecordFormat::Gif(path) => {
+                if self.gif.is_none() {
+                    let mut encoder = gif::Encoder::new(File::create(path)?, width as u16, height as u16, &[])?;
+                    encoder.set_repeat(gif::Repeat::Infinite)?;
+                    self.gif = Some(encoder);
+                }
+                let mut frame = gif::Frame::from_rgb_speed(width as u16, height as u16, pixels, 10);
+                frame.delay = (100.0 / self.frame_rate).round() as u16; // gif delays are in hundredths of a second
+                if let Some(encoder) = self.gif.as_mut() {
+                    encoder.write_frame(&frame)?;
+                }
+            }
+        }
+        self.frame_count += 1;
+        return Ok(());
+    }
+
+    // dropping the encoder writes the gif trailer
+    pub fn finish(mut self) -> usize {
+        self.gif = None;
+        return self.frame_count;
+    }
+}
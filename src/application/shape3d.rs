@@ -1,31 +1,77 @@
-use glam::{DVec3, DQuat, DMat4};
-use sdl2::{render::Canvas, video::Window, rect::Point, pixels::Color};
-use crate::application::Axes;
+use std::{collections::{HashMap, HashSet}, f64::consts::{PI, TAU, SQRT_2}, sync::{Arc, OnceLock}};
+
+use glam::{DVec3, DQuat};
+use sdl2::pixels::Color;
+use crate::application::{Axes, label::Label, projection::Projection, viewport::Viewport, depth_cue::DepthCue, line::{Stroke, LineTarget}, style::{self, Style}, bounds::{Aabb, BoundingSphere, Obb}, frustum::Frustum, mesh::Mesh, hull, parallel, catalogue};
 const PHI: f64 = 1.61803398874989484820;
 
-#[derive(Debug)]
+// an instance of a mesh: where it is, which way it faces, how large it is and how it looks.
+// cloning a shape only copies the instance, the mesh is shared
+#[derive(Debug, Clone)]
 pub struct Shape3d {
-    vertices: Vec<DVec3>,
-    edges: Vec<[usize; 2]>,
+    name: String,
+    mesh: Arc<Mesh>,
     location: DVec3,
+    orientation: DQuat,
+    scale: DVec3,
     local_axes: Axes,
-    axes_hidden: bool
+    axes_hidden: bool,
+    style: Style,
+    // transformed on first use after the shape last moved, copies share them until either one moves
+    world_vertices: OnceLock<Arc<Vec<DVec3>>>,
 }
 
 impl Shape3d {
     pub fn default() -> Self {
-        let verticies: Vec<DVec3> = vec![];
-        let edges: Vec<[usize; 2]> = vec![];
-        return Self { 
-            vertices: verticies,
-            edges,
-            location: DVec3::ZERO,
-            local_axes: Axes::default(),
-            axes_hidden: true
-        }
+        return Self::from_mesh(Arc::new(Mesh::new(vec![], vec![])), DVec3::ZERO, DVec3::ONE);
     }
+    // the vertices are given in world space, the shape gets a mesh of its own
     pub fn new(vertices: Vec<DVec3>, edges: Vec<[usize; 2]>, location: DVec3) -> Self {
-        return Self { vertices, edges, local_axes: Axes::new(DVec3::X, DVec3::Y, DVec3::Z, location), location, axes_hidden: true }
+        let vertices = vertices.iter().map(|vertex| *vertex - location).collect();
+        return Self::from_mesh(Arc::new(Mesh::new(vertices, edges)), location, DVec3::ONE);
+    }
+    // the edges are every side of every face, each shared side only once
+    pub fn with_faces<F: AsRef<[usize]>>(vertices: Vec<DVec3>, faces: Vec<F>, location: DVec3) -> Self {
+        let vertices = vertices.iter().map(|vertex| *vertex - location).collect();
+        return Self::from_mesh(Arc::new(Mesh::with_faces(vertices, faces)), location, DVec3::ONE);
+    }
+    // places the mesh unrotated at location, scaled by scale along its own axes
+    pub fn from_mesh(mesh: Arc<Mesh>, location: DVec3, scale: DVec3) -> Self {
+        return Self {
+            name: String::new(),
+            mesh,
+            location,
+            orientation: DQuat::IDENTITY,
+            scale,
+            local_axes: Axes::new(DVec3::X, DVec3::Y, DVec3::Z, location),
+            axes_hidden: true,
+            style: Style::default(),
+            world_vertices: OnceLock::new(),
+        };
+    }
+    pub fn name(&self) -> &str {
+        return &self.name;
+    }
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+    pub fn mesh(&self) -> &Arc<Mesh> {
+        return &self.mesh;
+    }
+    pub fn style(&self) -> &Style {
+        return &self.style;
+    }
+    pub fn style_mut(&mut self) -> &mut Style {
+        return &mut self.style;
+    }
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+    pub fn stroke(&self) -> Stroke {
+        return self.style.stroke;
+    }
+    pub fn set_stroke(&mut self, stroke: Stroke) {
+        self.style.stroke = stroke;
     }
     pub fn show_axes(&mut self) {
         self.axes_hidden = false;
@@ -40,258 +86,625 @@ impl Shape3d {
             z: self.location.z,
         }
     }
-    pub fn vertices(&self) -> &Vec<DVec3> {
-        return &self.vertices;
+    // world space vertices, transformed from the mesh only once after every move
+    pub fn vertices(&self) -> &[DVec3] {
+        return self.world_vertices.get_or_init(|| {
+            let mut vertices = self.mesh.vertices().clone();
+            let (orientation, scale, location) = (self.orientation, self.scale, self.location);
+            parallel::transform(&mut vertices, |vertex| orientation.mul_vec3(vertex * scale) + location);
+            return Arc::new(vertices);
+        });
+    }
+    fn vertex(&self, index: usize) -> Option<DVec3> {
+        return self.vertices().get(index).copied();
+    }
+    fn moved(&mut self) {
+        self.world_vertices = OnceLock::new();
+    }
+    pub fn edges(&self) -> &Vec<[usize; 2]> {
+        return self.mesh.edges();
+    }
+    pub fn faces(&self) -> &Vec<Vec<usize>> {
+        return self.mesh.faces();
+    }
+    // indices of the faces on either side of each edge, in the same order as edges()
+    pub fn edge_faces(&self) -> &Vec<Vec<usize>> {
+        return self.mesh.edge_faces();
+    }
+    // keeps the current edges, use with_faces to derive them. a mesh shared with other shapes is copied first
+    pub fn set_faces(&mut self, faces: Vec<Vec<usize>>) {
+        Arc::make_mut(&mut self.mesh).set_faces(faces);
+    }
+    // newell's method, so slightly non-planar faces still get a sensible outward normal
+    pub fn face_normal(&self, face_index: usize) -> Option<DVec3> {
+        let face = self.faces().get(face_index)?;
+        let mut normal = DVec3::ZERO;
+        for (index, vertex_index) in face.iter().enumerate() {
+            let vertex = self.vertex(*vertex_index)?;
+            let next = self.vertex(face[(index + 1) % face.len()])?;
+            normal += vertex.cross(next);
+        }
+        return Some(normal.normalize_or_zero());
+    }
+    pub fn face_center(&self, face_index: usize) -> Option<DVec3> {
+        let face = self.faces().get(face_index)?;
+        let mut center = DVec3::ZERO;
+        for vertex_index in face.iter() {
+            center += self.vertex(*vertex_index)?;
+        }
+        return Some(center / face.len() as f64);
+    }
+    pub fn scale(&self) -> DVec3 {
+        return self.scale;
+    }
+    pub fn orientation(&self) -> DQuat {
+        return self.orientation;
+    }
+    pub fn set_transform(&mut self, location: &DVec3, orientation: &DQuat, scale: &DVec3) {
+        let colors = self.local_axes.colors();
+        self.local_axes = Axes::new(
+            orientation.mul_vec3(DVec3::X),
+            orientation.mul_vec3(DVec3::Y),
+            orientation.mul_vec3(DVec3::Z),
+            *location,
+        );
+        self.local_axes.set_colors(colors);
+        self.location = *location;
+        self.orientation = orientation.normalize();
+        self.scale = *scale;
+        self.moved();
+    }
+    pub fn aabb(&self) -> Option<Aabb> {
+        return Aabb::from_points(self.vertices());
+    }
+
+    // the mesh's sphere moved along with the instance, so it does not have to be found again over the world vertices.
+    // a scale that differs along the axes stretches the sphere, the largest one keeps it around every vertex
+    pub fn bounding_sphere(&self) -> Option<BoundingSphere> {
+        let sphere = self.mesh.bounding_sphere()?;
+        return Some(BoundingSphere {
+            center: self.orientation.mul_vec3(sphere.center * self.scale) + self.location,
+            radius: sphere.radius * self.scale.abs().max_element(),
+        });
+    }
+
+    // the principal component box, unless the box along the shape's own axes is smaller as it is for most generated shapes
+    pub fn obb(&self) -> Option<Obb> {
+        let vertices = self.vertices();
+        let principal = Obb::from_points(vertices)?;
+        let local_x = self.local_axes.x().normalize_or_zero();
+        let local_y = self.local_axes.y().normalize_or_zero();
+        let local_z = local_x.cross(local_y);
+        if local_z == DVec3::ZERO {
+            return Some(principal);
+        }
+        return match Obb::from_axes(vertices, [local_x, local_y, local_z]) {
+            Some(local) if local.volume() <= principal.volume() * (1.0 + 1e-9) => Some(local),
+            _ => Some(principal),
+        };
+    }
+
+    pub fn name_label(&self) -> Label {
+        return Label::new(&self.name, self.location, Color::WHITE);
+    }
+    pub fn vertex_labels(&self) -> Vec<Label> {
+        return self.vertices().iter()
+            .enumerate()
+            .map(|(index, vertex)| Label::new(&index.to_string(), *vertex, Color::GRAY))
+            .collect();
     }
+    // only the instance moves, the mesh stays in the shape's own coordinates
     pub fn rotate(&mut self, rotation_center: &DVec3, rotation_axis: &DVec3, angle_radians: &f64) {
         if rotation_axis.length() != 0.0 {
             self.local_axes.rotate(rotation_center, rotation_axis, angle_radians);
             let rotation: DQuat = DQuat::from_axis_angle(*rotation_axis, *angle_radians);
-            for vertex in self.vertices.iter_mut() {
-                *vertex -= *rotation_center;
-                *vertex  =  rotation.mul_vec3(*vertex);
-                *vertex += *rotation_center;
-            }
+            self.orientation = (rotation * self.orientation).normalize();
             self.location -= *rotation_center;
             self.location  =  rotation.mul_vec3(self.location);
             self.location += *rotation_center;
+            self.moved();
         }
     }
     pub fn translate(&mut self, translation_axis: &DVec3, distance: &f64) {
         if translation_axis.length() != 0.0 {
             self.local_axes.translate(translation_axis, distance);
-            let delta_vertex = translation_axis.normalize() * (*distance);
-            for vertex in self.vertices.iter_mut() {
-                *vertex += delta_vertex;
-            }
-            self.location += delta_vertex;
+            self.location += translation_axis.normalize() * (*distance);
+            self.moved();
         }
     }
-    pub fn draw_orthographic(&mut self, canvas: &mut Canvas<Window>) -> Result<(), String> {
+    pub fn draw_axes<T: LineTarget>(&self, target: &mut T, viewport: &Viewport, projection: &Projection) -> Result<(), String> {
         if !self.axes_hidden {
-            self.local_axes.draw_orthographic(canvas, &100.0)?;
-        }
-        let (center_x, center_y) = ((canvas.window().size().0/2) as f64, (canvas.window().size().1/2) as f64);
-        canvas.set_draw_color(Color::WHITE);
-        for edge in self.edges.iter() {
-            let vertex_a =  self.vertices.get(edge[0]);
-            let vertex_b = self.vertices.get(edge[1]);
-            if vertex_a.is_none() || vertex_b.is_none() {
-                return Err(format!("missing at least one vertex of edge number {edge:?}").to_string());
-            }
-            let vertex_a = vertex_a.unwrap();
-            let vertex_b = vertex_b.unwrap();
-
-            let start: Point = Point::new(
-                (vertex_a.x + center_x) as i32,
-                (vertex_a.y + center_y) as i32);
-
-            let end:   Point = Point::new(
-                (vertex_b.x + center_x) as i32,
-                (vertex_b.y + center_y) as i32);
-
-            canvas.draw_line(start, end)?;
+            self.local_axes.draw(target, viewport, &100.0, projection)?;
         }
         return Ok(());
     }
 
-    pub fn draw_perspective(&mut self, canvas: &mut Canvas<Window>, perspective: &DMat4) -> Result<(), String> {
-        if !self.axes_hidden {
-            self.local_axes.draw_orthographic(canvas, &100.0)?;
+    // index and clipped, projected end points of every edge that can be seen, in screen space with the depth in z.
+    // edges plainly outside the frustum are rejected first, then the vertices of the remaining edges are projected once.
+    // only edges crossing the near plane are clipped and projected on their own
+    pub fn projected_edges(&self, projection: &Projection, viewport: &Viewport) -> Result<Vec<(usize, DVec3, DVec3)>, String> {
+        let frustum = Frustum::new(projection, viewport);
+        let vertices = self.vertices();
+        let mut kept_edges = Vec::with_capacity(self.edges().len());
+        let mut used = vec![false; vertices.len()];
+        for (edge_index, edge) in self.edges().iter().enumerate() {
+            let (vertex_a, vertex_b) = match (vertices.get(edge[0]), vertices.get(edge[1])) {
+                (Some(vertex_a), Some(vertex_b)) => (vertex_a, vertex_b),
+                _ => return Err(format!("missing at least one vertex of edge number {edge:?}")),
+            };
+            if frustum.rejects_segment(vertex_a, vertex_b) {
+                continue;
+            }
+            used[edge[0]] = true;
+            used[edge[1]] = true;
+            kept_edges.push(edge_index);
         }
-        let [center_x, center_y] = [(canvas.window().size().0/2) as f64, (canvas.window().size().1/2) as f64];
-        canvas.set_draw_color(Color::WHITE);
-        for edge in self.edges.iter() {
-            let vertex_a =  self.vertices.get(edge[0]);
-            let vertex_b = self.vertices.get(edge[1]);
-            if vertex_a.is_none() || vertex_b.is_none() {
-                return Err(format!("missing at least one vertex of edge number {edge:?}").to_string());
+        let projected_vertices = parallel::project_used(vertices, &used, projection, viewport);
+        let mut projected_edges = Vec::with_capacity(kept_edges.len());
+        for edge_index in kept_edges {
+            let edge = &self.edges()[edge_index];
+            if let (Some(start), Some(end)) = (projected_vertices[edge[0]], projected_vertices[edge[1]]) {
+                projected_edges.push((edge_index, start, end));
+            } else if let Some((start, end)) = projection.project_segment(&vertices[edge[0]], &vertices[edge[1]], viewport) {
+                projected_edges.push((edge_index, start, end));
             }
-            
-            // maybe use interior mutability?? instead of copying
-            let mut vertex_a = *vertex_a.unwrap();
-            let mut vertex_b = *vertex_b.unwrap();
-
-            vertex_a = perspective.project_point3(vertex_a);
-            vertex_b = perspective.project_point3(vertex_b);
-            let start: Point = Point::new(
-                (vertex_a.x + center_x) as i32,
-                (vertex_a.y + center_y) as i32);
-            let end:   Point = Point::new(
-                (vertex_b.x + center_x) as i32,
-                (vertex_b.y + center_y) as i32);
-            canvas.draw_line(start, end)?;
         }
-        return Ok(());
+        return Ok(projected_edges);
     }
 
-    pub fn draw_weak_perspective(&mut self, canvas: &mut Canvas<Window>) -> Result<(), String> {
-        if !self.axes_hidden {
-            self.local_axes.draw_orthographic(canvas, &100.0)?;
+    // returns how many edges were inside the view and drawn
+    pub fn draw<T: LineTarget>(&self, target: &mut T, viewport: &Viewport, projection: &Projection, depth_cue: &DepthCue) -> Result<usize, String> {
+        self.draw_axes(target, viewport, projection)?;
+        let projected_edges = self.projected_edges(projection, viewport)?;
+        for (edge_index, start, end) in projected_edges.iter() {
+            let colors = self.style.edge_colors(*edge_index, &self.edges()[*edge_index]);
+            style::draw_edge(target, start, end, colors, self.style.pattern, &self.style.stroke, depth_cue)?;
         }
-        let [center_x, center_y] = [(canvas.window().size().0/2) as f64, (canvas.window().size().1/2) as f64];
-        canvas.set_draw_color(Color::WHITE);
-        for edge in self.edges.iter() {
-            let mut vertex_a = *self.vertices.get(edge[0]).unwrap();
-            let mut vertex_b = *self.vertices.get(edge[1]).unwrap();
-            vertex_a *= vertex_a.distance(DVec3::ZERO) / vertex_a.z;
-            vertex_b *= vertex_b.distance(DVec3::ZERO) / vertex_b.z;
-            let start: Point = Point::new(
-                (vertex_a.x + center_x) as i32,
-                (vertex_a.y + center_y) as i32);
-            let end:   Point = Point::new(
-                (vertex_b.x + center_x) as i32,
-                (vertex_b.y + center_y) as i32);
-            canvas.draw_line(start, end)?;
-        }
-        return Ok(());
+        return Ok(projected_edges.len());
     }
 }
 
+// generated shapes are built at unit size and scaled by their instance, the platonic solids build their mesh only once
+fn solid(name: &str, mesh: &Arc<Mesh>, scale: f64, location: DVec3) -> Shape3d {
+    let mut shape = Shape3d::from_mesh(mesh.clone(), location, DVec3::splat(scale));
+    shape.set_name(name);
+    return shape;
+}
 pub fn cube(scale: f64, location: DVec3) -> Shape3d {
-    let mut cube: Shape3d = Shape3d::new(
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| Arc::new(Mesh::with_faces(
         vec![
-            DVec3::new( scale,  scale,  scale),
-            DVec3::new( scale,  scale, -scale),
-            DVec3::new( scale, -scale,  scale),
-            DVec3::new( scale, -scale, -scale),
-            DVec3::new(-scale,  scale,  scale),
-            DVec3::new(-scale,  scale, -scale),
-            DVec3::new(-scale, -scale,  scale),
-            DVec3::new(-scale, -scale, -scale)
+            DVec3::new( 1.0,      1.0,      1.0),
+            DVec3::new( 1.0,      1.0,     -1.0),
+            DVec3::new( 1.0,     -1.0,      1.0),
+            DVec3::new( 1.0,     -1.0,     -1.0),
+            DVec3::new(-1.0,      1.0,      1.0),
+            DVec3::new(-1.0,      1.0,     -1.0),
+            DVec3::new(-1.0,     -1.0,      1.0),
+            DVec3::new(-1.0,     -1.0,     -1.0)
         ],
         vec![
-            [0, 1], [0, 2], [0, 4],
-            [1, 3], [1, 5],
-            [2, 3], [2, 6],
-            [3, 7],
-            [4, 5], [4, 6],
-            [5, 7],
-            [6, 7]
-        ],
-        location,
-    );
-    for vertex in cube.vertices.iter_mut() {
-        *vertex += cube.location;
-    }
-    return cube;
+            [0, 1, 5, 4], [0, 2, 3, 1], [0, 4, 6, 2],
+            [1, 3, 7, 5], [2, 6, 7, 3], [4, 5, 7, 6]
+        ]
+    )));
+    return solid("cube", mesh, scale, location);
 }
 pub fn tetrahedron(scale: f64, location: DVec3) -> Shape3d {
-    let mut tetrahedron: Shape3d = Shape3d::new( 
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| Arc::new(Mesh::with_faces(
         vec![
-            DVec3::new( scale,  scale,  scale),  
-            DVec3::new(-scale, -scale,  scale),
-            DVec3::new(-scale,  scale, -scale),
-            DVec3::new( scale, -scale, -scale)
+            DVec3::new( 1.0,      1.0,      1.0),
+            DVec3::new(-1.0,     -1.0,      1.0),
+            DVec3::new(-1.0,      1.0,     -1.0),
+            DVec3::new( 1.0,     -1.0,     -1.0)
         ],
         vec![
-            [0, 1], [0, 2], [0, 3],
-            [1, 2], [1, 3],
-            [2, 3]
-        ],
-        location,
-    );
-    for vertex in tetrahedron.vertices.iter_mut() {
-        *vertex += tetrahedron.location;
-    }
-    return tetrahedron;
+            [0, 1, 3], [0, 2, 1], [0, 3, 2],
+            [1, 2, 3]
+        ]
+    )));
+    return solid("tetrahedron", mesh, scale, location);
 }
 pub fn octahedron(scale: f64, location: DVec3) -> Shape3d {
-    let mut octahedron: Shape3d = Shape3d::new( 
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| Arc::new(Mesh::with_faces(
         vec![
-            DVec3::new( scale, 0.0,    0.0), 
-            DVec3::new(-scale, 0.0,    0.0),
-            DVec3::new( 0.0,   scale,  0.0),
-            DVec3::new( 0.0,  -scale,  0.0),
-            DVec3::new( 0.0,   0.0,    scale), 
-            DVec3::new( 0.0,   0.0,   -scale)
+            DVec3::new( 1.0,      0.0,      0.0),
+            DVec3::new(-1.0,      0.0,      0.0),
+            DVec3::new( 0.0,      1.0,      0.0),
+            DVec3::new( 0.0,     -1.0,      0.0),
+            DVec3::new( 0.0,      0.0,      1.0),
+            DVec3::new( 0.0,      0.0,     -1.0)
         ],
         vec![
-            [0, 2], [0, 3], [0, 4], [0, 5],
-            [1, 2], [1, 3], [1, 4], [1, 5],
-            [2, 4], [2, 5],
-            [3, 4], [3, 5]
-        ],
-        location,
-    );
-    for vertex in octahedron.vertices.iter_mut() {
-        *vertex += octahedron.location;
-    }
-    return octahedron;
+            [0, 2, 4], [0, 3, 5], [0, 4, 3], [0, 5, 2],
+            [1, 2, 5], [1, 3, 4], [1, 4, 2], [1, 5, 3]
+        ]
+    )));
+    return solid("octahedron", mesh, scale, location);
 }
 pub fn dodecahedron(scale: f64, location: DVec3) -> Shape3d {
-    let mut dodecahedron: Shape3d = Shape3d::new( 
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| Arc::new(Mesh::with_faces(
         vec![
-            DVec3::new( scale,      scale,      scale),      
-            DVec3::new( scale,      scale,     -scale),      
-            DVec3::new( scale,     -scale,      scale),       
-            DVec3::new( scale,     -scale,     -scale),
-            DVec3::new(-scale,      scale,      scale),     
-            DVec3::new(-scale,      scale,     -scale),     
-            DVec3::new(-scale,     -scale,      scale),      
-            DVec3::new(-scale,     -scale,     -scale),
-            DVec3::new( 0.0,        scale/PHI,  scale*PHI),
-            DVec3::new( 0.0,        scale/PHI, -scale*PHI),
-            DVec3::new( 0.0,       -scale/PHI,  scale*PHI),
-            DVec3::new( 0.0,       -scale/PHI, -scale*PHI),
-            DVec3::new( scale/PHI,  scale*PHI,  0.0),
-            DVec3::new( scale/PHI, -scale*PHI,  0.0),
-            DVec3::new(-scale/PHI,  scale*PHI,  0.0), 
-            DVec3::new(-scale/PHI, -scale*PHI,  0.0),
-            DVec3::new( scale*PHI,  0.0,        scale/PHI),
-            DVec3::new( scale*PHI,  0.0,       -scale/PHI),
-            DVec3::new(-scale*PHI,  0.0,        scale/PHI), 
-            DVec3::new(-scale*PHI,  0.0,       -scale/PHI)
+            DVec3::new( 1.0,      1.0,      1.0),
+            DVec3::new( 1.0,      1.0,     -1.0),
+            DVec3::new( 1.0,     -1.0,      1.0),
+            DVec3::new( 1.0,     -1.0,     -1.0),
+            DVec3::new(-1.0,      1.0,      1.0),
+            DVec3::new(-1.0,      1.0,     -1.0),
+            DVec3::new(-1.0,     -1.0,      1.0),
+            DVec3::new(-1.0,     -1.0,     -1.0),
+            DVec3::new( 0.0,      1.0/PHI,  PHI),
+            DVec3::new( 0.0,      1.0/PHI, -PHI),
+            DVec3::new( 0.0,     -1.0/PHI,  PHI),
+            DVec3::new( 0.0,     -1.0/PHI, -PHI),
+            DVec3::new( 1.0/PHI,  PHI,      0.0),
+            DVec3::new( 1.0/PHI, -PHI,      0.0),
+            DVec3::new(-1.0/PHI,  PHI,      0.0),
+            DVec3::new(-1.0/PHI, -PHI,      0.0),
+            DVec3::new( PHI,      0.0,      1.0/PHI),
+            DVec3::new( PHI,      0.0,     -1.0/PHI),
+            DVec3::new(-PHI,      0.0,      1.0/PHI),
+            DVec3::new(-PHI,      0.0,     -1.0/PHI)
         ],
         vec![
-            [0,  8], [0, 12], [ 0, 16], [ 1,  9], [ 1, 12], [ 1, 17],
-            [2, 10], [2, 13], [ 2, 16], [ 3, 11], [ 3, 13], [ 3, 17],
-            [4,  8], [4, 14], [ 4, 18], [ 5,  9], [ 5, 14], [ 5, 19],
-            [6, 10], [6, 15], [ 6, 18], [ 7, 11], [ 7, 15], [ 7, 19],
-            [8, 10], [9, 11], [12, 14], [13, 15], [16, 17], [18, 19]
-        ],
-        location,
-    );
-    for vertex in dodecahedron.vertices.iter_mut() {
-        *vertex += dodecahedron.location;
-    }
-    return dodecahedron;
+            [0,  8, 10,  2, 16], [0, 12, 14,  4,  8], [0, 16, 17,  1, 12],
+            [1,  9,  5, 14, 12], [1, 17,  3, 11,  9], [2, 10,  6, 15, 13],
+            [2, 13,  3, 17, 16], [3, 13, 15,  7, 11], [4, 14,  5, 19, 18],
+            [4, 18,  6, 10,  8], [5,  9, 11,  7, 19], [6, 18, 19,  7, 15]
+        ]
+    )));
+    return solid("dodecahedron", mesh, scale, location);
 }
 pub fn icosahedron(scale: f64, location: DVec3) -> Shape3d {
-    let mut icosahedron: Shape3d = Shape3d::new( 
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| Arc::new(Mesh::with_faces(
         vec![
-            DVec3::new( 0.0,        scale,     scale*PHI),
-            DVec3::new( 0.0,        scale,    -scale*PHI),
-            DVec3::new( 0.0,       -scale,     scale*PHI),
-            DVec3::new( 0.0,       -scale,    -scale*PHI),
-            DVec3::new( scale,      scale*PHI, 0.0),
-            DVec3::new( scale,     -scale*PHI, 0.0),
-            DVec3::new(-scale,      scale*PHI, 0.0),
-            DVec3::new(-scale,     -scale*PHI, 0.0),
-            DVec3::new( scale*PHI,  0.0,       scale),
-            DVec3::new( scale*PHI,  0.0,      -scale),
-            DVec3::new(-scale*PHI,  0.0,       scale),
-            DVec3::new(-scale*PHI,  0.0,      -scale)
+            DVec3::new( 0.0,      1.0,      PHI),
+            DVec3::new( 0.0,      1.0,     -PHI),
+            DVec3::new( 0.0,     -1.0,      PHI),
+            DVec3::new( 0.0,     -1.0,     -PHI),
+            DVec3::new( 1.0,      PHI,      0.0),
+            DVec3::new( 1.0,     -PHI,      0.0),
+            DVec3::new(-1.0,      PHI,      0.0),
+            DVec3::new(-1.0,     -PHI,      0.0),
+            DVec3::new( PHI,      0.0,      1.0),
+            DVec3::new( PHI,      0.0,     -1.0),
+            DVec3::new(-PHI,      0.0,      1.0),
+            DVec3::new(-PHI,      0.0,     -1.0)
         ],
         vec![
-            [ 0,  2], [0,  4], [0, 6], [0,  8], [0, 10],
-            [ 1,  3], [1,  4], [1, 6], [1,  9], [1, 11],
-            [ 2,  5], [2,  7], [2, 8], [2, 10],
-            [ 3,  5], [3,  7], [3, 9], [3, 11],
-            [ 4,  6], [4,  8], [4, 9],
-            [ 5,  7], [5,  8], [5, 9],
-            [ 6, 10], [6, 11],
-            [ 7, 10], [7, 11],
-            [ 8,  9],
-            [10, 11]
-        ],
-        location,
-    );
-    for vertex in icosahedron.vertices.iter_mut() {
-        *vertex += icosahedron.location;
+            [0,  2,  8], [0,  4,  6], [0,  6, 10], [0,  8,  4], [0, 10,  2],
+            [1,  3, 11], [1,  4,  9], [1,  6,  4], [1,  9,  3], [1, 11,  6],
+            [2,  5,  8], [2,  7,  5], [2, 10,  7],
+            [3,  5,  7], [3,  7, 11], [3,  9,  5],
+            [4,  8,  9], [5,  9,  8], [6, 11, 10], [7, 10, 11]
+        ]
+    )));
+    return solid("icosahedron", mesh, scale, location);
+}
+// rings bands of latitude between the poles on the y axis, each cut into segments quads, triangles at the poles
+pub fn uv_sphere(radius: f64, rings: usize, segments: usize, location: DVec3) -> Shape3d {
+    let rings = rings.max(2);
+    let profile: Vec<(f64, f64)> = (1..rings)
+        .map(|ring| PI * ring as f64 / rings as f64)
+        .map(|theta| (theta.sin(), theta.cos()))
+        .collect();
+    return solid("uv_sphere", &Arc::new(lathe(&profile, segments, Some(1.0), Some(-1.0))), radius, location);
+}
+// every face of the icosahedron split into frequency squared triangles, then pushed out onto the sphere
+pub fn icosphere(radius: f64, frequency: usize, location: DVec3) -> Shape3d {
+    let frequency = frequency.max(1);
+    let icosahedron = icosahedron(1.0, DVec3::ZERO);
+    let base_vertices = icosahedron.mesh().vertices();
+    let mut vertices: Vec<DVec3> = vec![];
+    // neighbouring faces create the points on their shared edge twice, the second one reuses the first
+    let mut indices: HashMap<[i64; 3], usize> = HashMap::new();
+    let mut faces = vec![];
+    for face in icosahedron.faces().iter() {
+        let (a, b, c) = (base_vertices[face[0]], base_vertices[face[1]], base_vertices[face[2]]);
+        let mut point = |i: usize, j: usize| {
+            let vertex = (a + (b - a) * i as f64 / frequency as f64 + (c - a) * j as f64 / frequency as f64).normalize();
+            let key = (vertex * 1e9).round().to_array().map(|value| value as i64);
+            return *indices.entry(key).or_insert_with(|| {
+                vertices.push(vertex);
+                vertices.len() - 1
+            });
+        };
+        for i in 0..frequency {
+            for j in 0..frequency - i {
+                faces.push(vec![point(i, j), point(i + 1, j), point(i, j + 1)]);
+                if i + j + 1 < frequency {
+                    faces.push(vec![point(i + 1, j), point(i + 1, j + 1), point(i, j + 1)]);
+                }
+            }
+        }
+    }
+    return solid("icosphere", &Arc::new(Mesh::with_faces(vertices, faces)), radius, location);
+}
+// a surface of revolution about the y axis. the profile rings go from top to bottom as (radius, y),
+// each end is closed by a pole at the given height or else by a flat cap
+fn lathe(profile: &[(f64, f64)], segments: usize, top: Option<f64>, bottom: Option<f64>) -> Mesh {
+    let segments = segments.max(3);
+    let mut vertices = vec![];
+    if let Some(y) = top {
+        vertices.push(DVec3::new(0.0, y, 0.0));
+    }
+    let first = vertices.len();
+    for (radius, y) in profile.iter() {
+        for segment in 0..segments {
+            let phi = TAU * segment as f64 / segments as f64;
+            vertices.push(DVec3::new(radius * phi.cos(), *y, radius * phi.sin()));
+        }
+    }
+    let index = |ring: usize, segment: usize| first + ring * segments + segment % segments;
+    let last = profile.len() - 1;
+    let mut faces = vec![];
+    match top {
+        Some(_) => (0..segments).for_each(|segment| faces.push(vec![0, index(0, segment + 1), index(0, segment)])),
+        None => faces.push((0..segments).rev().map(|segment| index(0, segment)).collect()),
+    }
+    for ring in 0..last {
+        for segment in 0..segments {
+            faces.push(vec![index(ring, segment), index(ring, segment + 1), index(ring + 1, segment + 1), index(ring + 1, segment)]);
+        }
+    }
+    match bottom {
+        Some(y) => {
+            vertices.push(DVec3::new(0.0, y, 0.0));
+            let pole = vertices.len() - 1;
+            (0..segments).for_each(|segment| faces.push(vec![pole, index(last, segment), index(last, segment + 1)]));
+        }
+        None => faces.push((0..segments).map(|segment| index(last, segment)).collect()),
+    }
+    return Mesh::with_faces(vertices, faces);
+}
+// a ring of major_segments tubes around the y axis, each tube a ring of minor_segments quads
+pub fn torus(scale: f64, location: DVec3, major_radius: f64, minor_radius: f64, major_segments: usize, minor_segments: usize) -> Shape3d {
+    let (major_segments, minor_segments) = (major_segments.max(3), minor_segments.max(3));
+    let mut vertices = vec![];
+    for major in 0..major_segments {
+        let phi = TAU * major as f64 / major_segments as f64;
+        let direction = DVec3::new(phi.cos(), 0.0, phi.sin());
+        for minor in 0..minor_segments {
+            let theta = TAU * minor as f64 / minor_segments as f64;
+            vertices.push(direction * (major_radius + minor_radius * theta.cos()) + DVec3::Y * minor_radius * theta.sin());
+        }
+    }
+    let index = |major: usize, minor: usize| (major % major_segments) * minor_segments + minor % minor_segments;
+    let mut faces = vec![];
+    for major in 0..major_segments {
+        for minor in 0..minor_segments {
+            faces.push(vec![index(major, minor), index(major, minor + 1), index(major + 1, minor + 1), index(major + 1, minor)]);
+        }
+    }
+    return solid("torus", &Arc::new(Mesh::with_faces(vertices, faces)), scale, location);
+}
+// radius 1 and centered on the y axis like the other round shapes, closed by flat caps
+pub fn cylinder(scale: f64, location: DVec3, height: f64, segments: usize) -> Shape3d {
+    let mesh = lathe(&[(1.0, height / 2.0), (1.0, -height / 2.0)], segments, None, None);
+    return solid("cylinder", &Arc::new(mesh), scale, location);
+}
+// the apex points up the y axis
+pub fn cone(scale: f64, location: DVec3, height: f64, segments: usize) -> Shape3d {
+    let mesh = lathe(&[(1.0, -height / 2.0)], segments, Some(height / 2.0), None);
+    return solid("cone", &Arc::new(mesh), scale, location);
+}
+// a cylinder of the given height with a hemisphere of rings bands on each end
+pub fn capsule(scale: f64, location: DVec3, height: f64, rings: usize, segments: usize) -> Shape3d {
+    let rings = rings.max(1);
+    let ring = |ring: usize, offset: f64| {
+        let theta = PI / 2.0 * ring as f64 / rings as f64;
+        return (theta.sin(), theta.cos() + offset);
+    };
+    let profile: Vec<(f64, f64)> = (1..=rings).map(|index| ring(index, height / 2.0))
+        .chain((rings..2 * rings).map(|index| ring(index, -height / 2.0)))
+        .collect();
+    let mesh = lathe(&profile, segments, Some(1.0 + height / 2.0), Some(-1.0 - height / 2.0));
+    return solid("capsule", &Arc::new(mesh), scale, location);
+}
+// two regular polygons of the given number of sides joined by squares. the circumradius is 1
+pub fn prism(scale: f64, location: DVec3, sides: usize, height: f64) -> Shape3d {
+    let mesh = lathe(&[(1.0, height / 2.0), (1.0, -height / 2.0)], sides, None, None);
+    return solid("prism", &Arc::new(mesh), scale, location);
+}
+// like the prism with the bottom polygon turned half a side, joined by a band of triangles
+pub fn antiprism(scale: f64, location: DVec3, sides: usize, height: f64) -> Shape3d {
+    let sides = sides.max(3);
+    let mut vertices = vec![];
+    for (y, turn) in [(height / 2.0, 0.0), (-height / 2.0, 0.5)] {
+        for side in 0..sides {
+            let phi = TAU * (side as f64 + turn) / sides as f64;
+            vertices.push(DVec3::new(phi.cos(), y, phi.sin()));
+        }
+    }
+    let top = |side: usize| side % sides;
+    let bottom = |side: usize| sides + side % sides;
+    let mut faces = vec![(0..sides).rev().map(top).collect::<Vec<usize>>()];
+    for side in 0..sides {
+        faces.push(vec![top(side), top(side + 1), bottom(side)]);
+        faces.push(vec![bottom(side), top(side + 1), bottom(side + 1)]);
     }
-    return icosahedron;
-}  
+    faces.push((0..sides).map(bottom).collect());
+    return solid("antiprism", &Arc::new(Mesh::with_faces(vertices, faces)), scale, location);
+}
+// every point made from the coordinate triples by permuting them and flipping their signs, for the
+// permutations and sign changes keep accepts given whether the permutation is odd and how many signs are flipped
+fn expand(triples: &[[f64; 3]], keep: impl Fn(bool, usize) -> bool) -> Vec<DVec3> {
+    const PERMUTATIONS: [([usize; 3], bool); 6] = [
+        ([0, 1, 2], false), ([1, 2, 0], false), ([2, 0, 1], false),
+        ([0, 2, 1], true), ([2, 1, 0], true), ([1, 0, 2], true),
+    ];
+    let mut points = vec![];
+    // flipping the sign of a zero gives the same point again
+    let mut seen: HashSet<[i64; 3]> = HashSet::new();
+    for triple in triples.iter() {
+        for (permutation, odd) in PERMUTATIONS.iter() {
+            for signs in 0..8 {
+                let minus = (0..3).filter(|axis| signs & (1 << axis) != 0).count();
+                if !keep(*odd, minus) {
+                    continue;
+                }
+                let point = DVec3::from_array([0, 1, 2].map(|axis| if signs & (1 << axis) != 0 { -triple[permutation[axis]] } else { triple[permutation[axis]] }));
+                if seen.insert((point * 1e9).round().to_array().map(|value| value as i64)) {
+                    points.push(point);
+                }
+            }
+        }
+    }
+    return points;
+}
+// the faces of a vertex transitive solid are its convex hull, scaled so every vertex is 1 from the center
+fn hull_mesh(vertices: Vec<DVec3>) -> Arc<Mesh> {
+    let radius = vertices.first().map(|vertex| vertex.length()).unwrap_or(1.0);
+    let vertices: Vec<DVec3> = vertices.iter().map(|vertex| *vertex / radius).collect();
+    let faces = hull::convex_hull_faces(&vertices);
+    return Arc::new(Mesh::with_faces(vertices, faces));
+}
+fn any_permutation(_odd: bool, _minus: usize) -> bool {
+    return true;
+}
+fn even_permutation(odd: bool, _minus: usize) -> bool {
+    return !odd;
+}
+pub fn truncated_tetrahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[3.0, 1.0, 1.0]], |_, minus| minus % 2 == 0)));
+    return solid("truncated_tetrahedron", mesh, scale, location);
+}
+pub fn cuboctahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[1.0, 1.0, 0.0]], any_permutation)));
+    return solid("cuboctahedron", mesh, scale, location);
+}
+pub fn truncated_cube(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[SQRT_2 - 1.0, 1.0, 1.0]], any_permutation)));
+    return solid("truncated_cube", mesh, scale, location);
+}
+pub fn truncated_octahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[0.0, 1.0, 2.0]], any_permutation)));
+    return solid("truncated_octahedron", mesh, scale, location);
+}
+pub fn rhombicuboctahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[1.0, 1.0, 1.0 + SQRT_2]], any_permutation)));
+    return solid("rhombicuboctahedron", mesh, scale, location);
+}
+pub fn truncated_cuboctahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[1.0, 1.0 + SQRT_2, 1.0 + 2.0 * SQRT_2]], any_permutation)));
+    return solid("truncated_cuboctahedron", mesh, scale, location);
+}
+pub fn snub_cube(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| {
+        // the tribonacci constant
+        let t = (1.0 + (19.0 + 3.0 * 33f64.sqrt()).cbrt() + (19.0 - 3.0 * 33f64.sqrt()).cbrt()) / 3.0;
+        // even permutations with an even number of plus signs and odd ones with an odd number, one of the two mirror images
+        return hull_mesh(expand(&[[1.0, 1.0 / t, t]], |odd, minus| (3 - minus) % 2 == odd as usize));
+    });
+    return solid("snub_cube", mesh, scale, location);
+}
+pub fn icosidodecahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[[0.0, 0.0, PHI], [0.5, PHI / 2.0, PHI * PHI / 2.0]], even_permutation)));
+    return solid("icosidodecahedron", mesh, scale, location);
+}
+pub fn truncated_dodecahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[
+        [0.0, 1.0 / PHI, 2.0 + PHI],
+        [1.0 / PHI, PHI, 2.0 * PHI],
+        [PHI, 2.0, PHI + 1.0],
+    ], even_permutation)));
+    return solid("truncated_dodecahedron", mesh, scale, location);
+}
+pub fn truncated_icosahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[
+        [0.0, 1.0, 3.0 * PHI],
+        [1.0, 2.0 + PHI, 2.0 * PHI],
+        [PHI, 2.0, PHI * PHI * PHI],
+    ], even_permutation)));
+    return solid("truncated_icosahedron", mesh, scale, location);
+}
+pub fn rhombicosidodecahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[
+        [1.0, 1.0, PHI * PHI * PHI],
+        [PHI * PHI, PHI, 2.0 * PHI],
+        [2.0 + PHI, 0.0, PHI * PHI],
+    ], even_permutation)));
+    return solid("rhombicosidodecahedron", mesh, scale, location);
+}
+pub fn truncated_icosidodecahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| hull_mesh(expand(&[
+        [1.0 / PHI, 1.0 / PHI, 3.0 + PHI],
+        [2.0 / PHI, PHI, 1.0 + 2.0 * PHI],
+        [1.0 / PHI, PHI * PHI, 3.0 * PHI - 1.0],
+        [2.0 * PHI - 1.0, 2.0, 2.0 + PHI],
+        [PHI, 3.0, 2.0 * PHI],
+    ], even_permutation)));
+    return solid("truncated_icosidodecahedron", mesh, scale, location);
+}
+pub fn snub_dodecahedron(scale: f64, location: DVec3) -> Shape3d {
+    static MESH: OnceLock<Arc<Mesh>> = OnceLock::new();
+    let mesh = MESH.get_or_init(|| {
+        // xi is the real root of xi^3 - 2 xi = phi, found with newton's method
+        let mut xi: f64 = 1.7;
+        for _ in 0..50 {
+            xi -= (xi * xi * xi - 2.0 * xi - PHI) / (3.0 * xi * xi - 2.0);
+        }
+        let (a, b) = (xi - 1.0 / xi, xi * PHI + PHI * PHI + PHI / xi);
+        // even permutations with an even number of plus signs
+        return hull_mesh(expand(&[
+            [2.0 * a, 2.0, 2.0 * b],
+            [a + b / PHI + PHI, -a * PHI + b + 1.0 / PHI, a / PHI + b * PHI - 1.0],
+            [a + b / PHI - PHI, a * PHI - b + 1.0 / PHI, a / PHI + b * PHI + 1.0],
+            [-a / PHI + b * PHI + 1.0, -a + b / PHI - PHI, a * PHI + b - 1.0 / PHI],
+            [-a / PHI + b * PHI - 1.0, a - b / PHI - PHI, a * PHI + b + 1.0 / PHI],
+        ], |odd, minus| !odd && (3 - minus) % 2 == 0));
+    });
+    return solid("snub_dodecahedron", mesh, scale, location);
+}
+pub fn from_name(name: &str, scale: f64, location: DVec3) -> Option<Shape3d> {
+    return match name {
+        "cube" => Some(cube(scale, location)),
+        "tetrahedron" => Some(tetrahedron(scale, location)),
+        "octahedron" => Some(octahedron(scale, location)),
+        "dodecahedron" => Some(dodecahedron(scale, location)),
+        "icosahedron" => Some(icosahedron(scale, location)),
+        "truncated_tetrahedron" => Some(truncated_tetrahedron(scale, location)),
+        "cuboctahedron" => Some(cuboctahedron(scale, location)),
+        "truncated_cube" => Some(truncated_cube(scale, location)),
+        "truncated_octahedron" => Some(truncated_octahedron(scale, location)),
+        "rhombicuboctahedron" => Some(rhombicuboctahedron(scale, location)),
+        "truncated_cuboctahedron" => Some(truncated_cuboctahedron(scale, location)),
+        "snub_cube" => Some(snub_cube(scale, location)),
+        "icosidodecahedron" => Some(icosidodecahedron(scale, location)),
+        "truncated_dodecahedron" => Some(truncated_dodecahedron(scale, location)),
+        "truncated_icosahedron" => Some(truncated_icosahedron(scale, location)),
+        "rhombicosidodecahedron" => Some(rhombicosidodecahedron(scale, location)),
+        "truncated_icosidodecahedron" => Some(truncated_icosidodecahedron(scale, location)),
+        "snub_dodecahedron" => Some(snub_dodecahedron(scale, location)),
+        "uv_sphere" => Some(uv_sphere(scale, 12, 24, location)),
+        "icosphere" => Some(icosphere(scale, 3, location)),
+        "torus" => Some(torus(scale, location, 1.0, 0.35, 24, 12)),
+        "cylinder" => Some(cylinder(scale, location, 2.0, 24)),
+        "cone" => Some(cone(scale, location, 2.0, 24)),
+        "capsule" => Some(capsule(scale, location, 1.0, 6, 24)),
+        // hexagonal with every edge as long as a side
+        "prism" => Some(prism(scale, location, 6, 1.0)),
+        "antiprism" => Some(antiprism(scale, location, 6, (1.0 - (2.0 * (PI / 12.0).sin()).powi(2)).sqrt())),
+        // the johnson and catalan solids by name or by index like J8 or C2
+        _ => catalogue::from_name(name, scale, location),
+    };
+}
 pub fn platonic_solids(scale: f64) -> Vec<Shape3d> {
     return vec![
         crate::application::shape3d::cube(        scale,        DVec3 { x: 0.0,    y: 0.0,    z: 100.0 }),
@@ -300,4 +713,33 @@ pub fn platonic_solids(scale: f64) -> Vec<Shape3d> {
         crate::application::shape3d::dodecahedron(scale * 0.75, DVec3 { x: 0.0,    y: 200.0 , z: 100.0 }),
         crate::application::shape3d::icosahedron( scale * 0.75, DVec3 { x: 0.0,    y:-200.0,  z: 100.0 }),
     ]
-}
\ No newline at end of file
+}
+
+// all 13 in rows of five, four and four, every vertex 1.2 scale from its center so they come out about as large as the platonic solids
+pub fn archimedean_solids(scale: f64) -> Vec<Shape3d> {
+    let generators: [fn(f64, DVec3) -> Shape3d; 13] = [
+        truncated_tetrahedron, cuboctahedron, truncated_cube, truncated_octahedron, rhombicuboctahedron,
+        truncated_cuboctahedron, snub_cube, icosidodecahedron, truncated_dodecahedron,
+        truncated_icosahedron, rhombicosidodecahedron, truncated_icosidodecahedron, snub_dodecahedron,
+    ];
+    let spacing = scale * 3.0;
+    return generators.iter()
+        .enumerate()
+        .map(|(index, generator)| {
+            let (row, column, columns) = match index {
+                0..=4 => (0, index, 5),
+                5..=8 => (1, index - 5, 4),
+                _ => (2, index - 9, 4),
+            };
+            let x = (column as f64 - (columns - 1) as f64 / 2.0) * spacing;
+            let y = (row as f64 - 1.0) * spacing;
+            return generator(scale * 1.2, DVec3::new(x, y, 100.0));
+        })
+        .collect();
+}
+
+// the box around every vertex of the shapes, None without any vertices
+pub fn bounding_box<'a>(shapes: impl IntoIterator<Item = &'a Shape3d>) -> Option<Aabb> {
+    let vertices: Vec<DVec3> = shapes.into_iter().flat_map(|shape| shape.vertices().iter().copied()).collect();
+    return Aabb::from_points(&vertices);
+}
@@ -0,0 +1,124 @@
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{depth_cue::DepthCue, line::{Stroke, LineTarget}};
+
+const SEGMENT_LENGTH: f64 = 8.0; // pixels per separately colored piece of a shaded or gradient edge
+
+// colors handed out by Style::from_palette so overlapping shapes can be told apart
+const PALETTE: [Color; 6] = [
+    Color::RGB(255, 255, 255),
+    Color::RGB(255, 120, 120),
+    Color::RGB(120, 255, 120),
+    Color::RGB(120, 160, 255),
+    Color::RGB(255, 220, 100),
+    Color::RGB(230, 130, 255),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinePattern {
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl LinePattern {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            LinePattern::Solid => "Solid",
+            LinePattern::Dashed => "Dashed",
+            LinePattern::Dotted => "Dotted",
+        };
+    }
+
+    pub fn next(&self) -> Self {
+        return match self {
+            LinePattern::Solid => LinePattern::Dashed,
+            LinePattern::Dashed => LinePattern::Dotted,
+            LinePattern::Dotted => LinePattern::Solid,
+        };
+    }
+
+    // lengths in pixels of the drawn and skipped parts, scaled with the line width so thick lines keep their look
+    fn lengths(&self, width: f64) -> Option<(f64, f64)> {
+        let width = width.max(1.0);
+        return match self {
+            LinePattern::Solid => None,
+            LinePattern::Dashed => Some((8.0 * width, 4.0 * width)),
+            LinePattern::Dotted => Some((width, 2.0 * width + 1.0)),
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub color: Color,
+    pub edge_colors: Option<Vec<Color>>, // one per edge, replaces the base color
+    pub vertex_colors: Option<Vec<Color>>, // one per vertex, blended along each edge, takes priority over edge colors
+    pub pattern: LinePattern,
+    pub stroke: Stroke,
+}
+
+impl Style {
+    pub fn default() -> Self {
+        return Self { color: Color::WHITE, edge_colors: None, vertex_colors: None, pattern: LinePattern::Solid, stroke: Stroke::default() };
+    }
+
+    pub fn from_palette(index: usize) -> Self {
+        return Self { color: PALETTE[index % PALETTE.len()], ..Self::default() };
+    }
+
+    // colors at the start and end of an edge, missing entries fall back to the base color
+    pub fn edge_colors(&self, edge_index: usize, edge: &[usize; 2]) -> (Color, Color) {
+        if let Some(vertex_colors) = &self.vertex_colors {
+            return (
+                *vertex_colors.get(edge[0]).unwrap_or(&self.color),
+                *vertex_colors.get(edge[1]).unwrap_or(&self.color),
+            );
+        }
+        if let Some(edge_color) = self.edge_colors.as_ref().and_then(|edge_colors| edge_colors.get(edge_index)) {
+            return (*edge_color, *edge_color);
+        }
+        return (self.color, self.color);
+    }
+}
+
+pub fn lerp_color(a: Color, b: Color, t: f64) -> Color {
+    let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    return Color::RGBA(channel(a.r, b.r), channel(a.g, b.g), channel(a.b, b.b), channel(a.a, b.a));
+}
+
+// start and end are projected points with the depth in z, colors are blended between them
+pub fn draw_edge<T: LineTarget>(
+    target: &mut T,
+    start: &DVec3,
+    end: &DVec3,
+    colors: (Color, Color),
+    pattern: LinePattern,
+    stroke: &Stroke,
+    depth_cue: &DepthCue,
+) -> Result<(), String> {
+    let length = start.truncate().distance(end.truncate());
+    let gradient = colors.0 != colors.1 || depth_cue.is_enabled();
+    let (on, off) = pattern.lengths(stroke.width).unwrap_or((length, 0.0));
+
+    if length == 0.0 {
+        return target.stroke_line(start, end, depth_cue.shade(colors.0, start.z), stroke);
+    }
+    // walk the edge a dash at a time, splitting dashes that change color on the way
+    let mut distance = 0.0;
+    while distance < length {
+        let dash_end = (distance + on).min(length);
+        let pieces = if gradient { ((dash_end - distance) / SEGMENT_LENGTH).ceil().max(1.0) as usize } else { 1 };
+        for piece in 0..pieces {
+            let t0 = (distance + (dash_end - distance) * piece as f64 / pieces as f64) / length;
+            let t1 = (distance + (dash_end - distance) * (piece + 1) as f64 / pieces as f64) / length;
+            let piece_start = start.lerp(*end, t0);
+            let piece_end = start.lerp(*end, t1);
+            let color = depth_cue.shade(lerp_color(colors.0, colors.1, (t0 + t1) / 2.0), (piece_start.z + piece_end.z) / 2.0);
+            target.stroke_line(&piece_start, &piece_end, color, stroke)?;
+        }
+        distance = dash_end + off;
+    }
+    return Ok(());
+}
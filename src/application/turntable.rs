@@ -0,0 +1,87 @@
+use std::{error::Error, f64::consts::TAU, path::PathBuf};
+
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{shape3d::Shape3d, recorder::{Recorder, RecordFormat}, framebuffer::Framebuffer, projection::{Projection, Lens}, line::Stroke};
+
+// fraction of the image the framed model spans, leaving a margin around it
+const FILL: f64 = 0.8;
+
+#[derive(Debug)]
+pub struct Turntable {
+    frames: usize,
+    axis: DVec3,
+    size: u32,
+    output: PathBuf, // a .gif file, otherwise a directory of numbered pngs
+    stroke: Option<Stroke>, // overrides the shape's own stroke
+}
+
+impl Turntable {
+    pub fn new(frames: usize, axis: DVec3, size: u32, output: PathBuf) -> Self {
+        return Self { frames, axis, size, output, stroke: None };
+    }
+
+    pub fn default() -> Self {
+        return Self { frames: 36, axis: DVec3::Y, size: 512, output: PathBuf::from("turntable"), stroke: None };
+    }
+
+    pub fn set_frames(&mut self, frames: usize) {
+        self.frames = frames;
+    }
+
+    pub fn set_axis(&mut self, axis: DVec3) {
+        self.axis = axis;
+    }
+
+    pub fn set_size(&mut self, size: u32) {
+        self.size = size;
+    }
+
+    pub fn set_output(&mut self, output: PathBuf) {
+        self.output = output;
+    }
+
+    pub fn set_stroke(&mut self, stroke: Stroke) {
+        self.stroke = Some(stroke);
+    }
+
+    // moves the shape to the world origin and scales it so it fits in the image whichever way it faces
+    fn frame(&self, shape: &mut Shape3d) {
+        let radius = shape.vertices().iter()
+            .map(|vertex| vertex.distance(shape.location()))
+            .fold(0.0, f64::max);
+        let scale = if radius > 0.0 { shape.scale() * (self.size as f64 * FILL / 2.0 / radius) } else { shape.scale() };
+        shape.set_transform(&DVec3::ZERO, &shape.orientation(), &scale);
+    }
+
+    // renders a full rotation of the shape about its own location, one image per step
+    pub fn render(&self, mut shape: Shape3d) -> Result<usize, Box<dyn Error>> {
+        if self.frames == 0 || self.axis.length() == 0.0 {
+            return Err("a turntable needs at least one frame and a non-zero axis".into());
+        }
+        let format = match self.output.extension() {
+            Some(extension) if extension == "gif" => RecordFormat::Gif(self.output.clone()),
+            _ => RecordFormat::PngSequence(self.output.clone()),
+        };
+        // a full turn plays back in three seconds when written as a gif
+        let mut recorder = Recorder::new(format, self.frames as f64 / 3.0, None)?;
+
+        self.frame(&mut shape);
+        if let Some(stroke) = self.stroke {
+            shape.set_stroke(stroke);
+        }
+        // drawn in memory so no window or video driver is needed
+        let mut framebuffer = Framebuffer::new(self.size, self.size);
+        let axis = self.axis.normalize();
+        let delta_angle = TAU / self.frames as f64;
+        for _ in 0..self.frames {
+            framebuffer.clear(Color::BLACK);
+            framebuffer.draw_shape(&shape, &Projection::new(Lens::Orthographic { scale: 1.0 }))?;
+            recorder.push_frame(self.size, self.size, framebuffer.pixels_mut())?;
+            let rotation_center = shape.location();
+            shape.rotate(&rotation_center, &axis, &delta_angle);
+        }
+        return Ok(recorder.finish());
+    }
+}
@@ -0,0 +1,50 @@
+use sdl2::{render::Canvas, video::Window, rect::Rect};
+
+// the part of the render target a scene is drawn into, in output pixels.
+// on high-dpi displays the output has more pixels than the window has points, pixel_scale is their ratio
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    origin: (i32, i32),
+    size: (u32, u32),
+    pixel_scale: f64,
+}
+
+impl Viewport {
+    pub fn new(origin: (i32, i32), size: (u32, u32), pixel_scale: f64) -> Self {
+        return Self { origin, size, pixel_scale };
+    }
+
+    // the whole drawable area of the canvas, which can be larger than canvas.window().size()
+    pub fn from_canvas(canvas: &Canvas<Window>) -> Result<Self, String> {
+        let output_size = canvas.output_size()?;
+        let window_width = canvas.window().size().0;
+        let pixel_scale = if window_width > 0 { output_size.0 as f64 / window_width as f64 } else { 1.0 };
+        return Ok(Self::new((0, 0), output_size, pixel_scale));
+    }
+
+    pub fn origin(&self) -> (i32, i32) {
+        return self.origin;
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        return self.size;
+    }
+
+    pub fn pixel_scale(&self) -> f64 {
+        return self.pixel_scale;
+    }
+
+    // where the world origin lands, in output pixels
+    pub fn center(&self) -> (f64, f64) {
+        return (self.origin.0 as f64 + (self.size.0/2) as f64, self.origin.1 as f64 + (self.size.1/2) as f64);
+    }
+
+    pub fn rect(&self) -> Rect {
+        return Rect::new(self.origin.0, self.origin.1, self.size.0.max(1), self.size.1.max(1));
+    }
+
+    // a part of this viewport, origin and size are relative to it and in its pixels
+    pub fn sub_viewport(&self, origin: (i32, i32), size: (u32, u32)) -> Self {
+        return Self::new((self.origin.0 + origin.0, self.origin.1 + origin.1), size, self.pixel_scale);
+    }
+}
@@ -1,9 +1,134 @@
 pub mod application;
 
-use crate::application::Application;
+use std::path::{Path, PathBuf};
+
+use glam::DVec3;
+use sdl2::pixels::Color;
+
+use crate::application::{Application, shape3d::{self, Shape3d}, catalogue, turntable::Turntable, line::Stroke, point_cloud::PointCloud, model::Model};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut frame_rate = 30.0;
+    let mut duration = None;
+    let mut depth_near_color = Color::WHITE;
+    let mut depth_far_color = Color::RGB(40, 40, 70);
+    let mut depth_range = None;
+    let mut stroke = Stroke::default();
+    let mut marker_size = None;
+    let mut pixels_per_unit = 1.0;
+    let mut scene = None;
+    let mut point_clouds = vec![];
+    let mut decimation = 1;
+    let mut benchmark_edges = None;
+    let mut frames = None;
+    let mut turntable_shape = None;
+    let mut turntable = Turntable::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fps" => frame_rate = args.next().ok_or("--fps needs a value")?.parse()?,
+            "--duration" => duration = Some(args.next().ok_or("--duration needs a value")?.parse()?),
+            "--depth-near-color" => depth_near_color = parse_color(&args.next().ok_or("--depth-near-color needs a value")?)?,
+            "--depth-far-color" => depth_far_color = parse_color(&args.next().ok_or("--depth-far-color needs a value")?)?,
+            "--depth-range" => {
+                let range = args.next().ok_or("--depth-range needs near,far")?;
+                let (near, far) = range.split_once(',').ok_or("--depth-range needs near,far")?;
+                depth_range = Some((near.trim().parse()?, far.trim().parse()?));
+            }
+            "--line-width" => stroke.width = args.next().ok_or("--line-width needs a value")?.parse()?,
+            "--antialias" => stroke.antialiased = true,
+            "--scene" => scene = Some(args.next().ok_or("--scene needs platonic, archimedean, johnson or catalan")?),
+            "--points" => point_clouds.push(PathBuf::from(args.next().ok_or("--points needs a path")?)),
+            "--decimate" => decimation = args.next().ok_or("--decimate needs a value")?.parse()?,
+            "--pixels-per-unit" => pixels_per_unit = args.next().ok_or("--pixels-per-unit needs a value")?.parse()?,
+            "--marker-size" => marker_size = Some(args.next().ok_or("--marker-size needs a value")?.parse()?),
+            "--benchmark" => benchmark_edges = Some(args.next().ok_or("--benchmark needs an edge count")?.parse()?),
+            "--turntable" => turntable_shape = Some(args.next().ok_or("--turntable needs a shape name, a model or a point cloud file")?),
+            "--frames" => frames = Some(args.next().ok_or("--frames needs a value")?.parse()?),
+            "--size" => turntable.set_size(args.next().ok_or("--size needs a value")?.parse()?),
+            "--output" => turntable.set_output(PathBuf::from(args.next().ok_or("--output needs a path")?)),
+            "--axis" => {
+                let axis = args.next().ok_or("--axis needs x, y or z")?;
+                turntable.set_axis(match axis.as_str() {
+                    "x" => DVec3::X,
+                    "y" => DVec3::Y,
+                    "z" => DVec3::Z,
+                    _ => return Err(format!("unknown axis {axis}").into()),
+                });
+            }
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+
+    if let Some(name) = turntable_shape {
+        // an obj or off model or a point cloud file works as well as a shape name
+        let shape = match shape3d::from_name(&name, 50.0, DVec3::ZERO) {
+            Some(shape) => shape,
+            None if Path::new(&name).is_file() && Model::is_model(Path::new(&name)) => load_model(Path::new(&name))?,
+            None if Path::new(&name).is_file() => load_point_cloud(Path::new(&name), decimation)?,
+            None => return Err(format!("unknown shape {name}").into()),
+        };
+        if let Some(frames) = frames {
+            turntable.set_frames(frames);
+        }
+        turntable.set_stroke(stroke);
+        let frames = turntable.render(shape)?;
+        println!("Rendered {frames} turntable frames");
+        return Ok(());
+    }
+
     let mut wireframe_viewer = Application::new("Wireframe Mode")?;
+    wireframe_viewer.set_recording(frame_rate, duration);
+    if let Some(scene) = scene {
+        wireframe_viewer.set_shapes(match scene.as_str() {
+            "platonic" => shape3d::platonic_solids(50.0),
+            "archimedean" => shape3d::archimedean_solids(50.0),
+            "johnson" => catalogue::johnson_solids(50.0),
+            "catalan" => catalogue::catalan_solids(50.0),
+            _ => return Err(format!("unknown scene {scene}").into()),
+        });
+    }
+    if !point_clouds.is_empty() {
+        let shapes = point_clouds.iter()
+            .map(|path| load_point_cloud(path, decimation))
+            .collect::<Result<Vec<_>, _>>()?;
+        wireframe_viewer.set_shapes(shapes);
+    }
+    wireframe_viewer.set_stroke(stroke);
+    wireframe_viewer.set_pixels_per_unit(pixels_per_unit);
+    if let Some(size) = marker_size {
+        wireframe_viewer.set_marker_size(size);
+    }
+    wireframe_viewer.set_depth_cue_colors(depth_near_color, depth_far_color);
+    wireframe_viewer.set_depth_cue_range(depth_range);
+    if let Some(edges) = benchmark_edges {
+        wireframe_viewer.benchmark(edges, frames.unwrap_or(100))?;
+        return Ok(());
+    }
     wireframe_viewer.run()?;
     return Ok(());
-}
\ No newline at end of file
+}
+
+fn load_point_cloud(path: &Path, decimation: usize) -> Result<Shape3d, Box<dyn std::error::Error>> {
+    let cloud = PointCloud::load(path, decimation).map_err(|error| format!("could not load {}: {error}", path.display()))?;
+    println!("Loaded {} points from {}", cloud.points().len(), path.display());
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    return Ok(cloud.into_shape(&name, 200.0, DVec3::ZERO));
+}
+
+fn load_model(path: &Path) -> Result<Shape3d, Box<dyn std::error::Error>> {
+    let model = Model::load(path).map_err(|error| format!("could not load {}: {error}", path.display()))?;
+    println!("Loaded {} vertices and {} faces from {}", model.vertices().len(), model.faces().len(), path.display());
+    let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    return Ok(model.into_shape(&name, 200.0, DVec3::ZERO));
+}
+
+// parses RRGGBB, with or without a leading #
+fn parse_color(hex: &str) -> Result<Color, Box<dyn std::error::Error>> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        return Err(format!("expected a RRGGBB color, got {hex}").into());
+    }
+    let value = u32::from_str_radix(hex, 16)?;
+    return Ok(Color::RGB((value >> 16) as u8, (value >> 8) as u8, value as u8));
+}